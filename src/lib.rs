@@ -1,20 +1,42 @@
-mod systems;
-mod ui;
+pub mod systems;
+pub mod ui;
 
 use bevy::prelude::*;
 use bevy_rapier3d::{ plugin::{ NoUserData, RapierPhysicsPlugin }, render::RapierDebugRenderPlugin };
-use systems::{ ScenePlugin, PlayerPlugin, MovementState };
+use systems::{
+    ScenePlugin,
+    PlayerPlugin,
+    MovementState,
+    MovementConfigPlugin,
+    LevelPlugin,
+    HealthPlugin,
+    DebugPlugin,
+    RumblePlugin,
+    VirtualJoystick,
+    Keybindings,
+    InputFrame,
+    InputCapture,
+};
 use ui::UiPlugin;
 
 pub fn run_app() {
     let mut app = App::new();
     app.init_resource::<MovementState>();
+    app.init_resource::<VirtualJoystick>();
+    app.init_resource::<Keybindings>();
+    app.init_resource::<InputFrame>();
+    app.init_resource::<InputCapture>();
     app.add_plugins(DefaultPlugins);
     app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
     app.add_plugins(RapierDebugRenderPlugin::default());
     app.add_plugins(ScenePlugin);
     app.add_plugins(UiPlugin);
     app.add_plugins(PlayerPlugin);
+    app.add_plugins(DebugPlugin);
+    app.add_plugins(RumblePlugin);
+    app.add_plugins(MovementConfigPlugin);
+    app.add_plugins(LevelPlugin);
+    app.add_plugins(HealthPlugin);
     app.run();
 }
 
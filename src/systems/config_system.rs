@@ -0,0 +1,156 @@
+use bevy::{
+    asset::{ io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState },
+    prelude::*,
+};
+use serde::{ Deserialize, Serialize };
+
+use crate::systems::MovementState;
+
+pub const MOVEMENT_CONFIG_PATH: &str = "movement.ron";
+
+pub struct MovementConfigPlugin;
+
+impl Plugin for MovementConfigPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<MovementConfig>();
+        app.register_asset_loader(MovementConfigLoader);
+        app.add_systems(Startup, load_movement_config);
+        app.add_systems(Update, apply_movement_config);
+    }
+}
+
+// Generates MovementConfig from the listed MovementState tuning fields so load/apply stay in sync.
+macro_rules! movement_config {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        /// Movement tuning loaded from `assets/movement.ron`. Missing keys keep their defaults.
+        #[derive(Asset, TypePath, Serialize, Deserialize, Clone, Debug)]
+        #[serde(default)]
+        pub struct MovementConfig {
+            $(pub $field: $ty,)*
+        }
+
+        impl MovementConfig {
+            pub fn from_state(st: &MovementState) -> Self {
+                Self {
+                    $($field: st.$field,)*
+                }
+            }
+
+            pub fn apply_to(&self, st: &mut MovementState) {
+                $(st.$field = self.$field;)*
+            }
+        }
+    };
+}
+
+movement_config! {
+    base_max_speed: f32,
+    max_back_speed: f32,
+    accel_k: f32,
+    decel_a: f32,
+    max_accel_per_s: f32,
+    hard_turn_dot: f32,
+    soft_turn_dot: f32,
+    soft_turn_speed_factor: f32,
+    stop_epsilon: f32,
+    hard_turn_hold_time: f32,
+    hard_turn_min_speed: f32,
+    launch_speed_fraction: f32,
+    sprint_multiplier: f32,
+    sprint_forward_only: bool,
+    crouch_speed: f32,
+    fall_decel_input: f32,
+    fall_decel_idle: f32,
+    fall_preserve_time: f32,
+    preserve_horizontal_at_terminal: bool,
+    gravity: f32,
+    gravity_ramp_per_s: f32,
+    gravity_ramp_max: f32,
+    jump_impulse: f32,
+    jump_requires_ground: bool,
+    coyote_time: f32,
+    jump_buffer_time: f32,
+    jump_cancels_hard_turn: bool,
+    air_control: f32,
+    dash_speed: f32,
+    dash_duration: f32,
+    dash_cooldown: f32,
+    double_tap_dash: bool,
+    double_tap_window: f32,
+    max_walkable_slope_angle: f32,
+    slope_turn_assist: bool,
+    slope_turn_assist_angle: f32,
+    edge_assist_speed: f32,
+    max_air_time: f32,
+}
+
+impl Default for MovementConfig {
+    fn default() -> Self {
+        Self::from_state(&MovementState::default())
+    }
+}
+
+#[derive(Default)]
+struct MovementConfigLoader;
+
+impl AssetLoader for MovementConfigLoader {
+    type Asset = MovementConfig;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>
+    ) -> Result<MovementConfig, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["movement.ron"]
+    }
+}
+
+/// Keeps the config handle alive; dropped if loading fails.
+#[derive(Resource)]
+pub struct MovementConfigHandle(pub Handle<MovementConfig>);
+
+fn load_movement_config(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(MovementConfigHandle(asset_server.load(MOVEMENT_CONFIG_PATH)));
+}
+
+// Copies the config into MovementState when it loads (and on reload with the `hot_reload` feature).
+fn apply_movement_config(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    handle: Option<Res<MovementConfigHandle>>,
+    configs: Res<Assets<MovementConfig>>,
+    mut events: EventReader<AssetEvent<MovementConfig>>,
+    mut st: ResMut<MovementState>
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+
+    if let Some(LoadState::Failed(err)) = asset_server.get_load_state(&handle.0) {
+        warn!("couldn't load {MOVEMENT_CONFIG_PATH} ({err}), using default movement tuning");
+        commands.remove_resource::<MovementConfigHandle>();
+        return;
+    }
+
+    for ev in events.read() {
+        let (AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id }) = ev else {
+            continue;
+        };
+        if *id != handle.0.id() {
+            continue;
+        }
+        if let Some(config) = configs.get(*id) {
+            config.apply_to(&mut st);
+            st.max_speed = st.base_max_speed;
+        }
+    }
+}
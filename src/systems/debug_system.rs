@@ -0,0 +1,296 @@
+use bevy::prelude::*;
+
+use crate::systems::{ update_grounded_flag_and_snap, MovementState, Player, PlayerDimensions };
+
+pub const DEBUG_TOGGLE_KEY: KeyCode = KeyCode::F3;
+pub const MOVEMENT_DUMP_KEY: KeyCode = KeyCode::F4;
+
+pub struct DebugPlugin;
+
+impl Plugin for DebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DebugSettings>();
+        app.init_resource::<DistanceRings>();
+        app.init_resource::<MovementLog>();
+        app.init_resource::<StateTint>();
+
+        app.add_systems(Update, (
+            toggle_debug,
+            dump_movement_state,
+            draw_distance_rings.run_if(debug_enabled),
+            tint_player_by_state,
+        ));
+
+        app.add_systems(
+            FixedUpdate,
+            log_movement.after(update_grounded_flag_and_snap).run_if(movement_log_enabled)
+        );
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct DebugSettings {
+    pub enabled: bool,
+}
+
+/// Concentric ground rings around the player for judging distances.
+#[derive(Resource)]
+pub struct DistanceRings {
+    pub radii: Vec<f32>,
+    pub color: Color,
+}
+
+impl Default for DistanceRings {
+    fn default() -> Self {
+        Self {
+            radii: vec![1.0, 2.0, 4.0, 8.0],
+            color: Color::srgba(1.0, 1.0, 1.0, 0.5),
+        }
+    }
+}
+
+/// Player mesh color per movement state while debug is on.
+#[derive(Resource)]
+pub struct StateTint {
+    pub enabled: bool,
+    pub grounded: Color,
+    pub hard_turn: Color,
+    pub falling: Color,
+}
+
+impl Default for StateTint {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            grounded: Color::srgb(0.2, 0.85, 0.3),
+            hard_turn: Color::srgb(0.95, 0.85, 0.2),
+            falling: Color::srgb(0.9, 0.2, 0.2),
+        }
+    }
+}
+
+/// Compact per-tick movement trace for bug reports, one line every `every_n` ticks.
+#[derive(Resource)]
+pub struct MovementLog {
+    pub enabled: bool,
+    pub every_n: u32,
+}
+
+impl Default for MovementLog {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            every_n: 8,
+        }
+    }
+}
+
+pub fn debug_enabled(debug: Res<DebugSettings>) -> bool {
+    debug.enabled
+}
+
+fn movement_log_enabled(log: Res<MovementLog>) -> bool {
+    log.enabled
+}
+
+fn toggle_debug(keys: Res<ButtonInput<KeyCode>>, mut debug: ResMut<DebugSettings>) {
+    if keys.just_pressed(DEBUG_TOGGLE_KEY) {
+        debug.enabled = !debug.enabled;
+    }
+}
+
+/// Rings sit at the player's XZ on the plane under its feet (lifted a hair to avoid z-fighting).
+pub fn ring_center(player_pos: Vec3, half_height: f32) -> Vec3 {
+    Vec3::new(player_pos.x, player_pos.y - half_height + 0.01, player_pos.z)
+}
+
+fn draw_distance_rings(
+    mut gizmos: Gizmos,
+    rings: Res<DistanceRings>,
+    dims: Res<PlayerDimensions>,
+    st: Res<MovementState>,
+    player_q: Query<&Transform, With<Player>>
+) {
+    let Ok(t) = player_q.get_single() else {
+        return;
+    };
+
+    let center = ring_center(t.translation, dims.current_half_height(st.is_crouching));
+
+    for &r in &rings.radii {
+        gizmos.circle(center, Dir3::Y, r, rings.color);
+    }
+}
+
+pub fn state_tint_color(st: &MovementState, tint: &StateTint) -> Color {
+    if st.is_falling {
+        tint.falling
+    } else if st.is_hard_turning() {
+        tint.hard_turn
+    } else {
+        tint.grounded
+    }
+}
+
+// Runs every frame (not gated on debug) so the original color comes back once debug is turned
+// off. Only RGB is touched; alpha belongs to the occlusion fade.
+fn tint_player_by_state(
+    mut base: Local<Option<Color>>,
+    debug: Res<DebugSettings>,
+    tint: Res<StateTint>,
+    st: Res<MovementState>,
+    player_q: Query<&Handle<StandardMaterial>, With<Player>>,
+    mut materials: ResMut<Assets<StandardMaterial>>
+) {
+    let Ok(handle) = player_q.get_single() else {
+        return;
+    };
+    let Some(current) = materials.get(handle).map(|m| m.base_color) else {
+        return;
+    };
+
+    let target = if debug.enabled && tint.enabled {
+        // remember the untinted color when tinting starts
+        base.get_or_insert(current);
+        state_tint_color(&st, &tint)
+    } else if let Some(original) = base.take() {
+        // tint just turned off: put the original color back, once
+        original
+    } else {
+        return;
+    };
+    let target = target.with_alpha(current.alpha());
+
+    // only touch the asset on change so it isn't re-uploaded every frame
+    if current != target {
+        if let Some(m) = materials.get_mut(handle) {
+            m.base_color = target;
+        }
+    }
+}
+
+fn log_movement(
+    mut tick: Local<u64>,
+    time: Res<Time>,
+    log: Res<MovementLog>,
+    st: Res<MovementState>
+) {
+    *tick += 1;
+    if !(*tick).is_multiple_of(u64::from(log.every_n.max(1))) {
+        return;
+    }
+
+    info!(
+        "tick={} dt={:.4} pressed={:?} speed={:.3} vel=({:.3}, {:.3}) fall_vel_y={:.3} falling={}",
+        *tick,
+        time.delta_seconds(),
+        st.pressed,
+        st.speed,
+        st.velocity.x,
+        st.velocity.y,
+        st.fall_vel_y,
+        st.is_falling
+    );
+}
+
+fn dump_movement_state(keys: Res<ButtonInput<KeyCode>>, st: Res<MovementState>) {
+    if keys.just_pressed(MOVEMENT_DUMP_KEY) {
+        info!("{}", st.debug_dump());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::log::tracing_subscriber::{ layer::{ Context, SubscriberExt }, Layer, Registry };
+    use bevy::utils::tracing::{ subscriber, Event, Subscriber };
+    use std::sync::{ atomic::{ AtomicUsize, Ordering }, Arc };
+
+    /// Counts every log event emitted while it is the active subscriber.
+    struct CountEvents(Arc<AtomicUsize>);
+
+    impl<S: Subscriber> Layer<S> for CountEvents {
+        fn on_event(&self, _event: &Event<'_>, _ctx: Context<'_, S>) {
+            self.0.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    fn world_with_player(color: Color) -> (World, Handle<StandardMaterial>) {
+        let mut world = World::new();
+        world.init_resource::<DebugSettings>();
+        world.init_resource::<StateTint>();
+        world.init_resource::<MovementState>();
+        let mut materials = Assets::<StandardMaterial>::default();
+        let handle = materials.add(StandardMaterial { base_color: color, ..default() });
+        world.insert_resource(materials);
+        world.spawn((Player, handle.clone()));
+        (world, handle)
+    }
+
+    fn color(world: &World, handle: &Handle<StandardMaterial>) -> Color {
+        world.resource::<Assets<StandardMaterial>>().get(handle).unwrap().base_color
+    }
+
+    fn set_alpha(world: &mut World, handle: &Handle<StandardMaterial>, alpha: f32) {
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        materials.get_mut(handle).unwrap().base_color.set_alpha(alpha);
+    }
+
+    #[test]
+    fn tint_keeps_fade_alpha_and_restores_once() {
+        let base = Color::srgb(0.2, 0.4, 0.6);
+        let (mut world, handle) = world_with_player(base);
+        // registered (not run_system_once) so the Local keeps the original color
+        let tint = world.register_system(tint_player_by_state);
+        world.resource_mut::<DebugSettings>().enabled = true;
+
+        // occlusion fade lowers alpha while tinted
+        set_alpha(&mut world, &handle, 0.3);
+        world.run_system(tint).unwrap();
+        let grounded = world.resource::<StateTint>().grounded;
+        assert_eq!(color(&world, &handle), grounded.with_alpha(0.3));
+
+        // turning debug off brings the original RGB back, still at the faded alpha
+        world.resource_mut::<DebugSettings>().enabled = false;
+        world.run_system(tint).unwrap();
+        assert_eq!(color(&world, &handle), base.with_alpha(0.3));
+
+        // from then on the color is left alone
+        let recolored = Color::srgb(1.0, 0.0, 0.0);
+        world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .get_mut(&handle)
+            .unwrap().base_color = recolored;
+        world.run_system(tint).unwrap();
+        assert_eq!(color(&world, &handle), recolored);
+    }
+
+    #[test]
+    fn movement_log_writes_one_line_every_n_ticks() {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        world.init_resource::<MovementState>();
+        world.insert_resource(MovementLog { enabled: true, every_n: 4 });
+        // registered so the tick counter survives between runs
+        let log = world.register_system(log_movement);
+
+        let lines = Arc::new(AtomicUsize::new(0));
+        let counter = Registry::default().with(CountEvents(lines.clone()));
+        let mut after = Vec::new();
+        subscriber::with_default(counter, || {
+            for _ in 0..12 {
+                world.run_system(log).unwrap();
+                after.push(lines.load(Ordering::Relaxed));
+            }
+        });
+
+        assert_eq!(after, [0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3]);
+    }
+
+    #[test]
+    fn rings_sit_just_above_the_feet_at_the_player_xz() {
+        let center = ring_center(Vec3::new(3.0, 5.0, -2.0), 1.0);
+        assert!(center.abs_diff_eq(Vec3::new(3.0, 4.01, -2.0), 1e-6));
+        assert_eq!(DistanceRings::default().radii, [1.0, 2.0, 4.0, 8.0]);
+    }
+}
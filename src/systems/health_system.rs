@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+
+use crate::systems::{ track_airtime, Landed, Player };
+
+pub struct HealthPlugin;
+
+impl Plugin for HealthPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FallDamage>();
+        app.add_systems(FixedUpdate, apply_fall_damage.after(track_airtime));
+    }
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct Health {
+    pub current: f32,
+    pub max: f32,
+}
+
+impl Default for Health {
+    fn default() -> Self {
+        Self { current: 100.0, max: 100.0 }
+    }
+}
+
+/// Landings faster than `min_fall_damage_speed` (units/s) cost `damage_per_speed`
+/// health per unit/s over it.
+#[derive(Resource)]
+pub struct FallDamage {
+    pub min_fall_damage_speed: f32,
+    pub damage_per_speed: f32,
+}
+
+impl Default for FallDamage {
+    fn default() -> Self {
+        Self {
+            min_fall_damage_speed: 12.0,
+            damage_per_speed: 10.0, // terminal fall speed (18 with default tuning) costs 60
+        }
+    }
+}
+
+impl FallDamage {
+    /// Damage for a landing at `impact_speed` (units/s, downward); 0 below the threshold.
+    pub fn damage(&self, impact_speed: f32) -> f32 {
+        (impact_speed - self.min_fall_damage_speed).max(0.0) * self.damage_per_speed.max(0.0)
+    }
+}
+
+fn apply_fall_damage(
+    fall_damage: Res<FallDamage>,
+    mut landings: EventReader<Landed>,
+    mut player_q: Query<&mut Health, With<Player>>
+) {
+    let Ok(mut health) = player_q.get_single_mut() else {
+        landings.clear();
+        return;
+    };
+
+    for landing in landings.read() {
+        let damage = fall_damage.damage(landing.impact_speed);
+        if damage <= 0.0 || health.current <= 0.0 {
+            continue;
+        }
+
+        health.current = (health.current - damage).max(0.0);
+        info!(
+            "fall damage {:.0} (landed at {:.1} u/s), health {:.0}/{:.0}",
+            damage,
+            landing.impact_speed,
+            health.current,
+            health.max
+        );
+
+        if health.current == 0.0 {
+            warn!("player would have died from a fall at {:.1} u/s", landing.impact_speed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn damage_starts_past_the_safe_speed() {
+        let fall = FallDamage::default();
+        assert_eq!(fall.damage(5.0), 0.0);
+        assert_eq!(fall.damage(fall.min_fall_damage_speed), 0.0);
+        assert_eq!(fall.damage(fall.min_fall_damage_speed + 2.0), 2.0 * fall.damage_per_speed);
+    }
+
+    #[test]
+    fn health_bottoms_out_at_zero() {
+        let mut world = World::new();
+        world.init_resource::<FallDamage>();
+        world.init_resource::<Events<Landed>>();
+        let player = world.spawn((Player, Health::default())).id();
+
+        world.send_event(Landed { impact_speed: 14.0, position: Vec3::ZERO });
+        world.run_system_once(apply_fall_damage);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 80.0);
+        world.resource_mut::<Events<Landed>>().clear();
+
+        world.send_event(Landed { impact_speed: 40.0, position: Vec3::ZERO });
+        world.run_system_once(apply_fall_damage);
+        assert_eq!(world.get::<Health>(player).unwrap().current, 0.0);
+    }
+}
@@ -0,0 +1,364 @@
+use bevy::{
+    asset::{ io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState },
+    prelude::*,
+};
+use bevy_rapier3d::prelude::*;
+use ron::ser::PrettyConfig;
+use serde::{ Deserialize, Serialize };
+
+use crate::systems::{ spawn_ground_block, spawn_wall, BoostPad, Ground, MovingPlatform, Wall };
+
+pub const SAVE_LEVEL_KEY: KeyCode = KeyCode::F5;
+// level loaded at startup (asset path)
+pub const LEVEL_PATH: &str = "levels/level0.ron";
+// F5 overwrites the startup level, which reloads live with the `hot_reload` feature
+pub const LEVEL_SAVE_PATH: &str = "assets/levels/level0.ron";
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<LevelFile>();
+        app.register_asset_loader(LevelFileLoader);
+        app.init_resource::<LevelStatus>();
+        app.add_systems(Startup, load_level);
+        app.add_systems(Update, (spawn_loaded_level, save_level_on_key));
+    }
+}
+
+/// Serializable snapshot of the level's Ground blocks and Walls (plain arrays so
+/// the file doesn't depend on bevy's serialize feature). Also the level asset
+/// loaded from LEVEL_PATH.
+#[derive(Asset, TypePath, Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct LevelFile {
+    pub grounds: Vec<GroundBlock>,
+    #[serde(default)]
+    pub walls: Vec<WallBlock>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct GroundBlock {
+    pub position: [f32; 3],
+    pub size: [f32; 3],
+    // sRGBA (not linear)
+    pub color: [f32; 4],
+    #[serde(default)]
+    pub boost_pad: Option<BoostPadBlock>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct WallBlock {
+    pub position: [f32; 3],
+    pub size: [f32; 3],
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub struct BoostPadBlock {
+    pub direction: [f32; 2],
+    pub speed: f32,
+}
+
+impl LevelFile {
+    pub fn to_ron(&self) -> Result<String, ron::Error> {
+        ron::ser::to_string_pretty(self, PrettyConfig::default())
+    }
+
+    pub fn from_ron(text: &str) -> Result<Self, ron::error::SpannedError> {
+        ron::from_str(text)
+    }
+
+    /// The built-in level: one 10x10 ground box with its top at y=0 and two walls on it.
+    pub fn fallback() -> Self {
+        Self {
+            grounds: vec![GroundBlock {
+                position: [0.0, -0.5, 0.0],
+                size: [10.0, 1.0, 10.0],
+                color: [0.3, 0.5, 0.3, 1.0],
+                boost_pad: None,
+            }],
+            walls: vec![
+                WallBlock { position: [3.5, 1.0, 0.0], size: [0.5, 2.0, 4.0] },
+                WallBlock { position: [-1.5, 1.0, -3.5], size: [4.0, 2.0, 0.5] },
+            ],
+        }
+    }
+
+    /// Spawns every block as a Ground (plus its tags) or Wall and returns the entities.
+    pub fn spawn(
+        &self,
+        commands: &mut Commands,
+        meshes: &mut Assets<Mesh>,
+        materials: &mut Assets<StandardMaterial>
+    ) -> Vec<Entity> {
+        let mut spawned = Vec::with_capacity(self.grounds.len() + self.walls.len());
+        for block in &self.grounds {
+            let [r, g, b, a] = block.color;
+            let e = spawn_ground_block(
+                commands,
+                meshes,
+                materials,
+                Vec3::from_array(block.position),
+                Vec3::from_array(block.size),
+                Color::srgba(r, g, b, a),
+            );
+
+            if let Some(pad) = block.boost_pad {
+                commands.entity(e).insert(BoostPad {
+                    direction: Vec2::from_array(pad.direction),
+                    speed: pad.speed,
+                });
+            }
+            spawned.push(e);
+        }
+
+        for wall in &self.walls {
+            let position = Vec3::from_array(wall.position);
+            spawned.push(spawn_wall(commands, meshes, materials, position, Vec3::from_array(wall.size)));
+        }
+        spawned
+    }
+}
+
+#[derive(Default)]
+struct LevelFileLoader;
+
+impl AssetLoader for LevelFileLoader {
+    type Asset = LevelFile;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        _load_context: &'a mut LoadContext<'_>
+    ) -> Result<LevelFile, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+/// Tracks the loaded level: its handle and the entities spawned from it
+/// (replaced on hot-reload). `ready` flips once something has been spawned.
+#[derive(Resource, Default)]
+pub struct LevelStatus {
+    pub handle: Option<Handle<LevelFile>>,
+    pub spawned: Vec<Entity>,
+    pub ready: bool,
+}
+
+/// Run condition: the level geometry exists (true when LevelPlugin isn't used).
+pub fn level_ready(status: Option<Res<LevelStatus>>) -> bool {
+    status.is_none_or(|s| s.ready)
+}
+
+fn load_level(asset_server: Res<AssetServer>, mut status: ResMut<LevelStatus>) {
+    status.handle = Some(asset_server.load(LEVEL_PATH));
+}
+
+// Spawns the level when it loads and respawns it on reload (`hot_reload` feature);
+// a missing/broken file falls back to the built-in level.
+fn spawn_loaded_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    levels: Res<Assets<LevelFile>>,
+    mut events: EventReader<AssetEvent<LevelFile>>,
+    mut status: ResMut<LevelStatus>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>
+) {
+    let Some(handle) = status.handle.clone() else {
+        return;
+    };
+
+    let level = if let Some(LoadState::Failed(err)) = asset_server.get_load_state(&handle) {
+        warn!("couldn't load {LEVEL_PATH} ({err}), using the built-in level");
+        status.handle = None;
+        LevelFile::fallback()
+    } else {
+        let changed = events.read().any(|ev| match ev {
+            AssetEvent::LoadedWithDependencies { id } | AssetEvent::Modified { id } => *id == handle.id(),
+            _ => false,
+        });
+        let Some(level) = levels.get(&handle).filter(|_| changed) else {
+            return;
+        };
+        level.clone()
+    };
+
+    for e in status.spawned.drain(..) {
+        commands.entity(e).despawn_recursive();
+    }
+    status.spawned = level.spawn(&mut commands, &mut meshes, &mut materials);
+    status.ready = true;
+}
+
+type GroundData<'a> = (
+    &'a Transform,
+    &'a Collider,
+    Option<&'a Handle<StandardMaterial>>,
+    Option<&'a BoostPad>,
+);
+
+type WallData<'a> = (&'a Transform, &'a Collider);
+
+/// Captures every static cuboid Ground (moving platforms aren't part of the file)
+/// and Wall. Non-cuboid colliders are skipped with a warning.
+pub fn capture_level(
+    grounds: &Query<GroundData, (With<Ground>, Without<MovingPlatform>)>,
+    walls: &Query<WallData, With<Wall>>,
+    materials: &Assets<StandardMaterial>
+) -> LevelFile {
+    let mut level = LevelFile::default();
+
+    for (t, collider, material, pad) in grounds {
+        let Some(cub) = collider.as_cuboid() else {
+            warn!("level save: skipping non-cuboid ground at {}", t.translation);
+            continue;
+        };
+
+        let color = material
+            .and_then(|h| materials.get(h))
+            .map_or(Color::WHITE, |m| m.base_color)
+            .to_srgba();
+
+        level.grounds.push(GroundBlock {
+            position: t.translation.to_array(),
+            size: (cub.half_extents() * 2.0).to_array(),
+            color: color.to_f32_array(),
+            boost_pad: pad.map(|p| BoostPadBlock {
+                direction: p.direction.to_array(),
+                speed: p.speed,
+            }),
+        });
+    }
+
+    for (t, collider) in walls {
+        let Some(cub) = collider.as_cuboid() else {
+            warn!("level save: skipping non-cuboid wall at {}", t.translation);
+            continue;
+        };
+
+        level.walls.push(WallBlock {
+            position: t.translation.to_array(),
+            size: (cub.half_extents() * 2.0).to_array(),
+        });
+    }
+
+    level
+}
+
+fn save_level_on_key(
+    keys: Res<ButtonInput<KeyCode>>,
+    materials: Res<Assets<StandardMaterial>>,
+    grounds: Query<GroundData, (With<Ground>, Without<MovingPlatform>)>,
+    walls: Query<WallData, With<Wall>>
+) {
+    if !keys.just_pressed(SAVE_LEVEL_KEY) {
+        return;
+    }
+
+    let level = capture_level(&grounds, &walls, &materials);
+    let text = match level.to_ron() {
+        Ok(text) => text,
+        Err(err) => {
+            warn!("level save: couldn't serialize ({err})");
+            return;
+        }
+    };
+
+    // no filesystem on the web build; this just reports the error there
+    match std::fs::write(LEVEL_SAVE_PATH, text) {
+        Ok(()) => info!(
+            "saved {} ground blocks and {} walls to {LEVEL_SAVE_PATH}",
+            level.grounds.len(),
+            level.walls.len()
+        ),
+        Err(err) => warn!("level save: couldn't write {LEVEL_SAVE_PATH} ({err})"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::{ system::RunSystemOnce, world::CommandQueue };
+
+    fn level_world() -> World {
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<StandardMaterial>>();
+        world
+    }
+
+    type Spawner<'a, 'w, 's> = (
+        &'a mut Commands<'w, 's>,
+        &'a mut Assets<Mesh>,
+        &'a mut Assets<StandardMaterial>,
+    );
+
+    // runs `spawn` with commands and asset storage, then applies the commands
+    fn spawn_with<T>(world: &mut World, spawn: impl FnOnce(Spawner) -> T) -> T {
+        world.resource_scope(|world, mut meshes: Mut<Assets<Mesh>>| {
+            world.resource_scope(|world, mut materials: Mut<Assets<StandardMaterial>>| {
+                let mut queue = CommandQueue::default();
+                let out = spawn((&mut Commands::new(&mut queue, world), &mut meshes, &mut materials));
+                queue.apply(world);
+                out
+            })
+        })
+    }
+
+    // captured blocks in a stable order (query order isn't)
+    fn capture(world: &mut World) -> LevelFile {
+        let mut level = world.run_system_once(
+            |grounds: Query<GroundData, (With<Ground>, Without<MovingPlatform>)>,
+             walls: Query<WallData, With<Wall>>,
+             materials: Res<Assets<StandardMaterial>>| capture_level(&grounds, &walls, &materials)
+        );
+        level.grounds.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+        level.walls.sort_by(|a, b| a.position.partial_cmp(&b.position).unwrap());
+        level
+    }
+
+    #[test]
+    fn saved_level_loads_back_the_same_blocks() {
+        let mut world = level_world();
+        spawn_with(&mut world, |(commands, meshes, materials)| {
+            let mut block = |position, size, color| {
+                spawn_ground_block(commands, meshes, materials, position, size, color)
+            };
+            block(Vec3::new(0.0, -0.5, 0.0), Vec3::new(10.0, 1.0, 10.0), Color::srgb(0.3, 0.5, 0.3));
+            let pad = block(Vec3::new(3.0, 0.05, -2.0), Vec3::new(2.0, 0.1, 3.0), Color::srgba(1.0, 0.6, 0.0, 0.8));
+            let lift = block(Vec3::Y, Vec3::ONE, Color::WHITE);
+            commands.entity(pad).insert(BoostPad { direction: Vec2::X, speed: 20.0 });
+            // moving platforms aren't saved
+            commands.entity(lift).insert(MovingPlatform::new(Vec3::Y, Vec3::Y * 4.0, 1.0));
+            spawn_wall(commands, meshes, materials, Vec3::new(-2.0, 1.0, 4.0), Vec3::new(4.0, 2.0, 0.5));
+        });
+
+        let saved = capture(&mut world);
+        assert_eq!((saved.grounds.len(), saved.walls.len()), (2, 1));
+        let loaded = LevelFile::from_ron(&saved.to_ron().unwrap()).unwrap();
+
+        let mut reloaded = level_world();
+        let spawned = spawn_with(&mut reloaded, |(commands, meshes, materials)| {
+            loaded.spawn(commands, meshes, materials)
+        });
+        assert_eq!(spawned.len(), 3);
+        assert_eq!(capture(&mut reloaded), saved);
+    }
+
+    #[test]
+    fn level_files_load_walls() {
+        let level = LevelFile::from_ron("(grounds: [])").unwrap();
+        assert!(level.walls.is_empty());
+
+        let shipped = LevelFile::from_ron(include_str!("../../assets/levels/level0.ron")).unwrap();
+        assert_eq!(shipped.walls, LevelFile::fallback().walls);
+    }
+}
@@ -1,7 +1,17 @@
 mod scene_system;
 mod movement_system;
 mod player_system;
+mod debug_system;
+mod rumble_system;
+mod config_system;
+mod level_system;
+mod health_system;
 
 pub use scene_system::*;
 pub use movement_system::*;
 pub use player_system::*;
+pub use debug_system::*;
+pub use rumble_system::*;
+pub use config_system::*;
+pub use level_system::*;
+pub use health_system::*;
@@ -1,6 +1,119 @@
 use bevy::prelude::*;
+use serde::{ Deserialize, Serialize };
+
+/// How opposing keys held together (W+S, A+D) resolve.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OpposingInputRule {
+    /// They sum to zero on that axis.
+    #[default]
+    Cancel,
+    /// The most recently pressed one wins.
+    LastWins,
+}
+
+// Per-axis key memory for OpposingInputRule::LastWins
+#[derive(Clone, Copy, Debug, Default)]
+struct AxisMemory {
+    held_pos: bool,
+    held_neg: bool,
+    last: f32,
+}
+
+impl AxisMemory {
+    fn resolve(&mut self, pos: bool, neg: bool, rule: OpposingInputRule) -> f32 {
+        if pos && !self.held_pos {
+            self.last = 1.0;
+        }
+        if neg && !self.held_neg {
+            self.last = -1.0;
+        }
+        self.held_pos = pos;
+        self.held_neg = neg;
+
+        match (pos, neg) {
+            (true, false) => 1.0,
+            (false, true) => -1.0,
+            (true, true) if rule == OpposingInputRule::LastWins => self.last,
+            _ => 0.0,
+        }
+    }
+}
+
+/// How the Sprint binding turns sprinting on.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SprintMode {
+    /// Sprint while the key is held.
+    #[default]
+    Hold,
+    /// Each tap flips sprint on/off; it also turns off when the player goes idle.
+    Toggle,
+}
+
+/// Which frame WASD is interpreted in.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ControlFrame {
+    /// W is always +Z, D is always +X; `camera_yaw` is ignored.
+    #[default]
+    World,
+    /// W moves away from the camera, D to the camera's right.
+    Camera,
+}
+
+/// On-screen stick input (x = right, y = forward, length <= 1), written by the UI.
+#[derive(Resource)]
+pub struct VirtualJoystick {
+    pub enabled: bool,
+    pub vector: Vec2,
+}
+
+impl Default for VirtualJoystick {
+    fn default() -> Self {
+        Self {
+            enabled: cfg!(target_arch = "wasm32"),
+            vector: Vec2::ZERO,
+        }
+    }
+}
+
+/// Drives the player along `dir` toward `target_speed` for `duration` seconds through
+/// the usual accel/decel curves, ignoring player input (cutscenes, conveyors).
+/// A newer event replaces the running one; `target_speed` 0 brakes to a stop.
+#[derive(Event, Clone, Copy, Debug, PartialEq)]
+pub struct ScriptedMove {
+    pub dir: Vec2,
+    pub target_speed: f32,
+    pub duration: f32,
+}
+
+/// One tick of player input, the only input movement_system sees. Captured from
+/// keys/sticks by `capture_input_frame`; replays or netcode can turn capture off
+/// (InputCapture) and write frames themselves. `jump`/`dash` are presses this tick.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct InputFrame {
+    // x = right, y = forward, length 0..1 (analog tilt)
+    pub move_dir: Vec2,
+    pub jump: bool,
+    pub sprint: bool,
+    pub crouch: bool,
+    pub dash: bool,
+}
 
 #[derive(Resource)]
+pub struct InputCapture {
+    pub enabled: bool,
+}
+
+impl Default for InputCapture {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+pub fn input_capture_enabled(capture: Res<InputCapture>) -> bool {
+    capture.enabled
+}
+
+#[derive(Resource, Debug)]
 pub struct MovementState {
     pub pressed: String,
 
@@ -8,10 +121,35 @@ pub struct MovementState {
     pub velocity: Vec2,
     pub speed: f32,
 
+    // exponential rate (1/s) the player mesh turns toward `dir` (player_system)
+    pub turn_rate: f32,
+
+    // effective top speed, rewritten every grounded tick from base_max_speed (x sprint)
     pub max_speed: f32,
+    pub base_max_speed: f32,
+    // top speed multiplier while Sprint is held with forward input
+    pub sprint_multiplier: f32,
+    pub is_sprinting: bool,
+    // sprint only with input toward the facing (control frame forward), not strafing/backpedaling
+    pub sprint_forward_only: bool,
+    pub sprint_mode: SprintMode,
+    // SprintMode::Toggle: sprint is switched on (still needs forward input)
+    pub sprint_toggled: bool,
+    // sprint input last tick, for tap detection
+    sprint_was_held: bool,
+    // Crouch held: top speed drops to crouch_speed, player_system shrinks the body
+    pub is_crouching: bool,
+    pub crouch_speed: f32,
+    // no headroom to stand up yet, stay crouched even with the key released (player_system)
+    pub crouch_blocked: bool,
+    // cap on the component of motion pointing backward (away from forward)
+    pub max_back_speed: f32,
     pub accel_k: f32,
     pub decel_a: f32,
 
+    // hard cap on speed gain per second, guards against dt spikes jumping the curve
+    pub max_accel_per_s: f32,
+
     pub hard_turn_dot: f32,
     pub soft_turn_dot: f32,
 
@@ -19,6 +157,8 @@ pub struct MovementState {
     pub stop_epsilon: f32,
 
     pub hard_turn_hold_time: f32,
+    // below this speed reversals just snap direction instead of pivoting
+    pub hard_turn_min_speed: f32,
     hard_turn_active: bool,
     hard_turn_timer: f32,
     pending_dir: Vec2,
@@ -27,17 +167,128 @@ pub struct MovementState {
     t: f32,
     start_speed: f32,
 
+    // fraction of max_speed applied instantly when starting from a full stop
+    pub launch_speed_fraction: f32,
+
     // set by ground detection (player_system)
     pub is_falling: bool,
+    // surface normal under the feet while grounded, Vec3::Y otherwise (player_system)
+    pub ground_normal: Vec3,
+    // ground entity under the feet while grounded (player_system)
+    pub standing_on: Option<Entity>,
+    // ground contact below the player's center, kept from the last grounded tick (player_system)
+    pub ground_point: Vec3,
+    // consecutive ticks grounded / airborne, the other one is 0 (player_system)
+    pub grounded_ticks: u32,
+    pub airborne_ticks: u32,
+    // degrees from horizontal; steeper surfaces don't count as ground and the player slides off
+    pub max_walkable_slope_angle: f32,
+    // on ground at least slope_turn_assist_angle (degrees) steep, input heading uphill
+    // is turned along the slope's contour so the player walks around the face
+    pub slope_turn_assist: bool,
+    pub slope_turn_assist_angle: f32,
+
+    // false = no snap-to-surface; grounding only pushes the player out of the ground
+    pub ground_snap_enabled: bool,
+
+    // player_system moves the player in this many slices per tick and stops a fall on
+    // the first slice that touches ground, so fast falls don't tunnel through thin platforms
+    pub movement_substeps: u32,
 
-    // horizontal decay while falling
-    pub fall_decel: f32,
+    // nudge the player back from a ledge they aren't moving toward (player_system)
+    pub edge_assist: bool,
+    pub edge_assist_speed: f32,
+
+    // horizontal decay while falling (steering vs. no input)
+    pub fall_decel_input: f32,
+    pub fall_decel_idle: f32,
+    // seconds after leaving the ground before that decay kicks in
+    pub fall_preserve_time: f32,
+    // stop that decay once terminal fall speed is reached (long falls keep momentum)
+    pub preserve_horizontal_at_terminal: bool,
 
     // ✅ NEW: vertical falling state (units/sec, negative down)
     pub fall_vel_y: f32,
 
     // ✅ NEW: gravity accel (units/sec^2, negative down)
     pub gravity: f32,
+
+    // upward fall_vel_y set by a jump (units/sec)
+    pub jump_impulse: f32,
+    // airborne because of a jump (horizontal speed is kept, not decayed)
+    pub is_jumping: bool,
+    // jump press latched in Update, consumed by the next FixedUpdate tick
+    jump_pressed: bool,
+    // false = jump any time, even mid-air (debug flying); true = ground/coyote only
+    pub jump_requires_ground: bool,
+    // grace window after walking off an edge in which a jump still fires
+    pub coyote_time: f32,
+    pub coyote_timer: f32,
+    // jumping mid hard-turn ends the pivot and launches toward pending_dir;
+    // off = the jump is buffered until the pivot finishes
+    pub jump_cancels_hard_turn: bool,
+    // a press made while airborne fires on landing if it's at most this old
+    pub jump_buffer_time: f32,
+    pub jump_buffer_timer: f32,
+
+    // Dash: hold dash_speed along `dir` for dash_duration (turns suspended),
+    // then the curves ease back; another dash needs dash_cooldown since the last one
+    pub dash_speed: f32,
+    pub dash_duration: f32,
+    pub dash_timer: f32,
+    pub dash_cooldown: f32,
+    pub dash_cooldown_timer: f32,
+    // latched like jump_pressed
+    dash_pressed: bool,
+    // also dash when a direction is pressed twice within double_tap_window seconds
+    pub double_tap_dash: bool,
+    pub double_tap_window: f32,
+    // seconds of movement ticks so far, and when each TAP_DIRS direction was last pressed
+    tap_clock: f32,
+    last_tap: [f32; 4],
+    tap_held: [bool; 4],
+
+    // running ScriptedMove (duration counts down), input is ignored while set
+    pub scripted: Option<ScriptedMove>,
+
+    // 0..1 share of accel_k used to steer toward input while airborne
+    // (0 = no steering, airborne input only picks the decay rate)
+    pub air_control: f32,
+
+    // gravity multiplier grows by this per second airborne, up to gravity_ramp_max
+    pub gravity_ramp_per_s: f32,
+    pub gravity_ramp_max: f32,
+
+    // fall_vel_y relative to terminal velocity (0 = apex/grounded, 1 = terminal),
+    // for fall animation blends / wind VFX
+    pub fall_intensity: f32,
+
+    // footstep cadence relative to max_speed (1.0 = full run, 0 while falling)
+    pub footstep_rate: f32,
+
+    // keep moving along `dir` without input (toggled by the AutoRun binding)
+    pub auto_run: bool,
+
+    pub opposing_input: OpposingInputRule,
+    input_axes: [AxisMemory; 2],
+
+    // gamepad left stick: tilt below this is ignored, the rest is rescaled to 0..1
+    // (half tilt = half speed)
+    pub stick_deadzone: f32,
+
+    pub control_frame: ControlFrame,
+    // follow camera yaw, only used with ControlFrame::Camera (player_system)
+    pub camera_yaw: f32,
+
+    // seconds since last grounded (player_system)
+    pub air_time: f32,
+
+    // height we left the ground at and the highest point since (player_system)
+    pub launch_y: f32,
+    pub peak_y: f32,
+
+    // after this long airborne, ground detection tries a long recovery raycast
+    pub max_air_time: f32,
 }
 
 impl Default for MovementState {
@@ -48,10 +299,25 @@ impl Default for MovementState {
             velocity: Vec2::ZERO,
             speed: 0.0,
 
+            turn_rate: 12.0,
+
             max_speed: 6.0,
+            base_max_speed: 6.0,
+            sprint_multiplier: 1.6,
+            is_sprinting: false,
+            sprint_forward_only: true,
+            sprint_mode: SprintMode::default(),
+            sprint_toggled: false,
+            sprint_was_held: false,
+            is_crouching: false,
+            crouch_speed: 2.0,
+            crouch_blocked: false,
+            max_back_speed: 6.0,
             accel_k: 6.0,
             decel_a: 6.0,
 
+            max_accel_per_s: 60.0,
+
             hard_turn_dot: -0.707,
             soft_turn_dot: 0.707,
 
@@ -59,6 +325,7 @@ impl Default for MovementState {
             stop_epsilon: 0.02,
 
             hard_turn_hold_time: 0.1,
+            hard_turn_min_speed: 0.0,
             hard_turn_active: false,
             hard_turn_timer: 0.0,
             pending_dir: Vec2::ZERO,
@@ -67,15 +334,349 @@ impl Default for MovementState {
             t: 0.0,
             start_speed: 0.0,
 
+            launch_speed_fraction: 0.1,
+
             is_falling: false,
-            fall_decel: 20.0,
+            ground_normal: Vec3::Y,
+            standing_on: None,
+            ground_point: Vec3::ZERO,
+            grounded_ticks: 0,
+            airborne_ticks: 0,
+            max_walkable_slope_angle: 45.0,
+            slope_turn_assist: false,
+            slope_turn_assist_angle: 30.0,
+            ground_snap_enabled: true,
+            movement_substeps: 1,
+
+            edge_assist: false,
+            edge_assist_speed: 1.5,
+            fall_decel_input: 10.0,
+            fall_decel_idle: 20.0,
+            fall_preserve_time: 0.0,
+            preserve_horizontal_at_terminal: false,
 
             fall_vel_y: 0.0,
             gravity: -30.0, // tune
+
+            jump_impulse: 10.0,
+            is_jumping: false,
+            jump_pressed: false,
+            jump_requires_ground: true,
+            coyote_time: 0.1,
+            coyote_timer: 0.0,
+            jump_cancels_hard_turn: true,
+            jump_buffer_time: 0.12,
+            jump_buffer_timer: 0.0,
+
+            dash_speed: 18.0,
+            dash_duration: 0.15,
+            dash_timer: 0.0,
+            dash_cooldown: 1.0,
+            dash_cooldown_timer: 0.0,
+            dash_pressed: false,
+            double_tap_dash: false,
+            double_tap_window: 0.25,
+            tap_clock: 0.0,
+            last_tap: [f32::NEG_INFINITY; 4],
+            tap_held: [false; 4],
+
+            scripted: None,
+
+            air_control: 0.0,
+
+            gravity_ramp_per_s: 0.0, // 0 = constant gravity
+            gravity_ramp_max: 2.0,
+
+            fall_intensity: 0.0,
+
+            footstep_rate: 0.0,
+
+            auto_run: false,
+
+            opposing_input: OpposingInputRule::default(),
+            input_axes: [AxisMemory::default(); 2],
+
+            stick_deadzone: 0.15,
+
+            control_frame: ControlFrame::default(),
+            camera_yaw: 0.0,
+
+            air_time: 0.0,
+            launch_y: 0.0,
+            peak_y: 0.0,
+            max_air_time: 5.0,
+        }
+    }
+}
+
+// Generates MovementSnapshot from the listed public (Copy) fields so snapshot/diff stay in sync.
+macro_rules! movement_snapshot {
+    ($($field:ident: $ty:ty),* $(,)?) => {
+        /// Copy of MovementState's public fields (minus `pressed`) for logging and assertions.
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub struct MovementSnapshot {
+            $(pub $field: $ty,)*
+        }
+
+        impl MovementState {
+            pub fn snapshot(&self) -> MovementSnapshot {
+                MovementSnapshot { $($field: self.$field,)* }
+            }
+        }
+
+        impl MovementSnapshot {
+            /// Names of the fields that differ between the two snapshots.
+            pub fn diff(&self, other: &MovementSnapshot) -> Vec<&'static str> {
+                let mut changed = Vec::new();
+                $(
+                    if self.$field != other.$field {
+                        changed.push(stringify!($field));
+                    }
+                )*
+                changed
+            }
+        }
+    };
+}
+
+movement_snapshot! {
+    dir: Vec2,
+    velocity: Vec2,
+    speed: f32,
+    turn_rate: f32,
+    max_speed: f32,
+    base_max_speed: f32,
+    sprint_multiplier: f32,
+    is_sprinting: bool,
+    sprint_forward_only: bool,
+    sprint_mode: SprintMode,
+    sprint_toggled: bool,
+    is_crouching: bool,
+    crouch_speed: f32,
+    crouch_blocked: bool,
+    max_back_speed: f32,
+    accel_k: f32,
+    decel_a: f32,
+    max_accel_per_s: f32,
+    hard_turn_dot: f32,
+    soft_turn_dot: f32,
+    soft_turn_speed_factor: f32,
+    stop_epsilon: f32,
+    hard_turn_hold_time: f32,
+    hard_turn_min_speed: f32,
+    launch_speed_fraction: f32,
+    is_falling: bool,
+    ground_normal: Vec3,
+    standing_on: Option<Entity>,
+    ground_point: Vec3,
+    grounded_ticks: u32,
+    airborne_ticks: u32,
+    max_walkable_slope_angle: f32,
+    slope_turn_assist: bool,
+    slope_turn_assist_angle: f32,
+    ground_snap_enabled: bool,
+    movement_substeps: u32,
+    edge_assist: bool,
+    edge_assist_speed: f32,
+    fall_decel_input: f32,
+    fall_decel_idle: f32,
+    fall_preserve_time: f32,
+    preserve_horizontal_at_terminal: bool,
+    fall_vel_y: f32,
+    gravity: f32,
+    jump_impulse: f32,
+    is_jumping: bool,
+    jump_requires_ground: bool,
+    coyote_time: f32,
+    coyote_timer: f32,
+    jump_cancels_hard_turn: bool,
+    jump_buffer_time: f32,
+    jump_buffer_timer: f32,
+    dash_speed: f32,
+    dash_duration: f32,
+    dash_timer: f32,
+    dash_cooldown: f32,
+    dash_cooldown_timer: f32,
+    double_tap_dash: bool,
+    double_tap_window: f32,
+    scripted: Option<ScriptedMove>,
+    air_control: f32,
+    gravity_ramp_per_s: f32,
+    gravity_ramp_max: f32,
+    fall_intensity: f32,
+    footstep_rate: f32,
+    auto_run: bool,
+    opposing_input: OpposingInputRule,
+    stick_deadzone: f32,
+    control_frame: ControlFrame,
+    camera_yaw: f32,
+    air_time: f32,
+    launch_y: f32,
+    peak_y: f32,
+    max_air_time: f32,
+}
+
+impl MovementState {
+    /// Stops all motion (speeds, fall, hard turn, curve timers) but keeps tuning
+    /// and facing. Use after teleports, cutscenes, etc.
+    pub fn reset_motion(&mut self) {
+        self.velocity = Vec2::ZERO;
+        self.speed = 0.0;
+        self.fall_vel_y = 0.0;
+        self.fall_intensity = 0.0;
+        self.footstep_rate = 0.0;
+        self.air_time = 0.0;
+        self.jump_buffer_timer = 0.0;
+        self.dash_timer = 0.0;
+
+        self.hard_turn_active = false;
+        self.hard_turn_timer = 0.0;
+        self.pending_dir = Vec2::ZERO;
+
+        self.accelerating = false;
+        self.t = 0.0;
+        self.start_speed = 0.0;
+    }
+
+    /// Every field (private ones included) with its current value, one per line.
+    pub fn debug_dump(&self) -> String {
+        format!("{self:#?}")
+    }
+
+    /// Launch along `dir` at `speed`; the curves decay it from there. Vertical state is untouched.
+    pub fn apply_boost(&mut self, dir: Vec2, speed: f32) {
+        let Some(dir) = dir.try_normalize() else { return; };
+
+        self.dir = dir;
+        self.speed = speed;
+        self.velocity = dir * speed;
+
+        self.hard_turn_active = false;
+        self.hard_turn_timer = 0.0;
+        self.pending_dir = Vec2::ZERO;
+
+        self.t = 0.0;
+        self.start_speed = speed;
+    }
+
+    /// Latches a jump press for the next movement tick, like the Jump binding does
+    /// (on-screen buttons, scripts).
+    pub fn press_jump(&mut self) {
+        self.jump_pressed = true;
+    }
+
+    /// Pivoting in place during a hard turn (speed held at 0 for `hard_turn_hold_time`).
+    pub fn is_hard_turning(&self) -> bool {
+        self.hard_turn_active
+    }
+
+    /// Fields that changed since `before` was taken.
+    pub fn diff(&self, before: &MovementSnapshot) -> Vec<&'static str> {
+        self.snapshot().diff(before)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputAction {
+    Forward,
+    Back,
+    Left,
+    Right,
+    Jump,
+    Sprint,
+    Crouch,
+    Dash,
+    AutoRun,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 9] = [
+        InputAction::Forward,
+        InputAction::Back,
+        InputAction::Left,
+        InputAction::Right,
+        InputAction::Jump,
+        InputAction::Sprint,
+        InputAction::Crouch,
+        InputAction::Dash,
+        InputAction::AutoRun,
+    ];
+}
+
+/// Keys for each movement action (WASD, Space, Shift, Ctrl, E, R by default).
+#[derive(Resource, Clone, Debug)]
+pub struct Keybindings {
+    pub forward: KeyCode,
+    pub back: KeyCode,
+    pub left: KeyCode,
+    pub right: KeyCode,
+    pub jump: KeyCode,
+    pub sprint: KeyCode,
+    pub crouch: KeyCode,
+    pub dash: KeyCode,
+    pub auto_run: KeyCode,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        Self {
+            forward: KeyCode::KeyW,
+            back: KeyCode::KeyS,
+            left: KeyCode::KeyA,
+            right: KeyCode::KeyD,
+            jump: KeyCode::Space,
+            sprint: KeyCode::ShiftLeft,
+            crouch: KeyCode::ControlLeft,
+            dash: KeyCode::KeyE,
+            auto_run: KeyCode::KeyR,
         }
     }
 }
 
+impl Keybindings {
+    pub fn key(&self, action: InputAction) -> KeyCode {
+        match action {
+            InputAction::Forward => self.forward,
+            InputAction::Back => self.back,
+            InputAction::Left => self.left,
+            InputAction::Right => self.right,
+            InputAction::Jump => self.jump,
+            InputAction::Sprint => self.sprint,
+            InputAction::Crouch => self.crouch,
+            InputAction::Dash => self.dash,
+            InputAction::AutoRun => self.auto_run,
+        }
+    }
+
+    pub fn rebind(&mut self, action: InputAction, key: KeyCode) {
+        let slot = match action {
+            InputAction::Forward => &mut self.forward,
+            InputAction::Back => &mut self.back,
+            InputAction::Left => &mut self.left,
+            InputAction::Right => &mut self.right,
+            InputAction::Jump => &mut self.jump,
+            InputAction::Sprint => &mut self.sprint,
+            InputAction::Crouch => &mut self.crouch,
+            InputAction::Dash => &mut self.dash,
+            InputAction::AutoRun => &mut self.auto_run,
+        };
+        *slot = key;
+    }
+
+    /// First action bound to `key`, if any.
+    pub fn action_for(&self, key: KeyCode) -> Option<InputAction> {
+        InputAction::ALL.into_iter().find(|&action| self.key(action) == key)
+    }
+
+    pub fn pressed(&self, keys: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keys.pressed(self.key(action))
+    }
+
+    pub fn just_pressed(&self, keys: &ButtonInput<KeyCode>, action: InputAction) -> bool {
+        keys.just_pressed(self.key(action))
+    }
+}
+
 #[inline]
 fn accel_exp(t: f32, k: f32) -> f32 {
     1.0 - (-k * t.max(0.0)).exp()
@@ -86,6 +687,14 @@ fn inv_square(t: f32, a: f32) -> f32 {
     1.0 / (1.0 + a * t.max(0.0)).powi(2)
 }
 
+/// Speeds `t` seconds into the accel curve (from rest) and the decel curve
+/// (from max_speed) with the current tuning. Used by the curve graph overlay.
+pub fn sample_speed_curves(st: &MovementState, t: f32) -> (f32, f32) {
+    let accel = st.max_speed * accel_exp(t, st.accel_k).clamp(0.0, 1.0);
+    let decel = st.max_speed * inv_square(t, st.decel_a);
+    (accel, decel)
+}
+
 #[inline]
 fn restart_curve(st: &mut MovementState, accelerating: bool) {
     if st.accelerating != accelerating {
@@ -95,46 +704,256 @@ fn restart_curve(st: &mut MovementState, accelerating: bool) {
     }
 }
 
-fn read_input_dir(keys: &ButtonInput<KeyCode>) -> Vec2 {
-    let mut raw = Vec2::ZERO;
-    if keys.pressed(KeyCode::KeyW) {
-        raw.y += 1.0;
+// Continue from the current speed after max_speed changes: below max, pick the point on the
+// accel curve that matches it; above max, the overspeed branch eases down from start_speed.
+fn retime_accel_curve(st: &mut MovementState) {
+    st.start_speed = st.speed;
+    st.t = if st.speed < st.max_speed {
+        -(1.0 - st.speed / st.max_speed).ln() / st.accel_k.max(0.001)
+    } else {
+        0.0
+    };
+}
+
+// right, left, forward, back in raw input
+const TAP_DIRS: [Vec2; 4] = [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y];
+
+/// Records presses of each raw input direction and returns the one pressed again within
+/// double_tap_window (None unless double_tap_dash is on).
+fn double_tap_dir(st: &mut MovementState, raw_input: Vec2) -> Option<Vec2> {
+    let mut tapped = None;
+    for (i, dir) in TAP_DIRS.into_iter().enumerate() {
+        let held = raw_input.dot(dir) > 0.5;
+        if held && !st.tap_held[i] {
+            if st.tap_clock - st.last_tap[i] <= st.double_tap_window {
+                tapped = Some(dir);
+                // a third tap starts a new pair
+                st.last_tap[i] = f32::NEG_INFINITY;
+            } else {
+                st.last_tap[i] = st.tap_clock;
+            }
+        }
+        st.tap_held[i] = held;
+    }
+    tapped.filter(|_| st.double_tap_dash)
+}
+
+fn start_jump(st: &mut MovementState) {
+    st.fall_vel_y = st.jump_impulse;
+    st.is_falling = true;
+    st.is_jumping = true;
+    st.coyote_timer = 0.0;
+    st.jump_buffer_timer = 0.0;
+}
+
+/// Strongest left stick over all connected gamepads, deadzoned and rescaled to 0..1.
+fn read_gamepad_stick(gamepads: &Gamepads, axes: &Axis<GamepadAxis>, deadzone: f32) -> Vec2 {
+    let mut best = Vec2::ZERO;
+    for gamepad in gamepads.iter() {
+        let axis = |axis_type| axes.get(GamepadAxis::new(gamepad, axis_type)).unwrap_or(0.0);
+        let v = Vec2::new(axis(GamepadAxisType::LeftStickX), axis(GamepadAxisType::LeftStickY));
+        if v.length_squared() > best.length_squared() {
+            best = v;
+        }
+    }
+
+    let len = best.length();
+    let dz = deadzone.clamp(0.0, 0.99);
+    if len <= dz {
+        return Vec2::ZERO;
     }
-    if keys.pressed(KeyCode::KeyS) {
-        raw.y -= 1.0;
+    best / len * ((len - dz) / (1.0 - dz)).min(1.0)
+}
+
+/// Movement input with analog magnitude (0..1): keyboard counts as full tilt, the
+/// gamepad and on-screen sticks as their tilt; the strongest one wins.
+fn read_input_dir(
+    keys: &ButtonInput<KeyCode>,
+    bindings: &Keybindings,
+    stick: &VirtualJoystick,
+    pad_stick: Vec2,
+    st: &mut MovementState
+) -> Vec2 {
+    let rule = st.opposing_input;
+    let [x_axis, y_axis] = &mut st.input_axes;
+    let held = |action| bindings.pressed(keys, action);
+
+    let mut raw = Vec2::new(
+        x_axis.resolve(held(InputAction::Right), held(InputAction::Left), rule),
+        y_axis.resolve(held(InputAction::Forward), held(InputAction::Back), rule),
+    );
+
+    raw = raw.normalize_or_zero();
+
+    let touch = if stick.enabled { stick.vector.clamp_length_max(1.0) } else { Vec2::ZERO };
+    for analog in [pad_stick, touch] {
+        if analog.length_squared() > raw.length_squared() {
+            raw = analog;
+        }
     }
-    if keys.pressed(KeyCode::KeyD) {
-        raw.x += 1.0;
+
+    raw
+}
+
+/// Maps raw input (x = right, y = forward) into movement space (x, y -> world x, z).
+fn to_control_frame(raw: Vec2, frame: ControlFrame, camera_yaw: f32) -> Vec2 {
+    match frame {
+        ControlFrame::World => raw,
+        ControlFrame::Camera => {
+            // yaw is the camera's heading, clockwise from -Z (PI/2 looks along +X)
+            let forward = Vec2::new(camera_yaw.sin(), -camera_yaw.cos());
+            let right = Vec2::new(-forward.y, forward.x);
+            right * raw.x + forward * raw.y
+        }
     }
-    if keys.pressed(KeyCode::KeyA) {
-        raw.x -= 1.0;
+}
+
+/// Drops the uphill part of `dir` on ground at least `min_angle` degrees steep, so
+/// pushing into a face walks along it. Straight uphill (nothing left) is kept as is.
+fn slope_assist_dir(dir: Vec2, ground_normal: Vec3, min_angle: f32) -> Vec2 {
+    let steepness = ground_normal.y.clamp(-1.0, 1.0).acos();
+    if dir == Vec2::ZERO || steepness < min_angle.to_radians() {
+        return dir;
     }
-    if raw.length_squared() > 0.0 {
-        raw.normalize()
-    } else {
-        Vec2::ZERO
+
+    // the normal leans downhill, so into the face = against it
+    let downhill = Vec2::new(ground_normal.x, ground_normal.z).normalize_or_zero();
+    let into = dir.dot(downhill);
+    if into >= 0.0 {
+        return dir;
     }
+
+    (dir - downhill * into).try_normalize().unwrap_or(dir)
+}
+
+/// Samples keys, sticks and the latched presses into this tick's InputFrame.
+pub fn capture_input_frame(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<Keybindings>,
+    stick: Res<VirtualJoystick>,
+    gamepads: Res<Gamepads>,
+    pad_axes: Res<Axis<GamepadAxis>>,
+    mut frame: ResMut<InputFrame>,
+    mut st: ResMut<MovementState>
+) {
+    let pad_stick = read_gamepad_stick(&gamepads, &pad_axes, st.stick_deadzone);
+
+    *frame = InputFrame {
+        move_dir: read_input_dir(&keys, &bindings, &stick, pad_stick, &mut st),
+        jump: std::mem::take(&mut st.jump_pressed),
+        sprint: bindings.pressed(&keys, InputAction::Sprint),
+        crouch: bindings.pressed(&keys, InputAction::Crouch),
+        dash: std::mem::take(&mut st.dash_pressed),
+    };
 }
 
+/// Steps MovementState by one FixedUpdate tick. Deterministic: it only reads the
+/// fixed timestep, the InputFrame, ScriptedMove events and the previous state (no
+/// wall clock, randomness or query iteration), so the same frame sequence from the
+/// same start state replays bit-identically for lockstep/replays.
 pub fn movement_system(
     time: Res<Time>,
-    keys: Res<ButtonInput<KeyCode>>,
+    input: Res<InputFrame>,
+    mut scripted_moves: EventReader<ScriptedMove>,
     mut st: ResMut<MovementState>
 ) {
     let dt = time.delta_seconds();
 
-    // terminal fall speed = 3x top move speed
-    let max_fall_speed = -3.0 * st.max_speed;
+    // terminal fall speed = 3x top move speed (unaffected by sprint/crouch)
+    let max_fall_speed = -3.0 * st.base_max_speed;
+
+    // scripted move: latest command wins, runs this tick and counts down
+    if let Some(ev) = scripted_moves.read().last() {
+        st.scripted = Some(*ev);
+    }
+    let script = st.scripted;
+    st.scripted = script
+        .map(|s| ScriptedMove { duration: s.duration - dt, ..s })
+        .filter(|s| s.duration > 0.0);
+    let input_live = script.is_none();
+
+    let raw_input = if input_live { input.move_dir.clamp_length_max(1.0) } else { Vec2::ZERO };
+    // direction and tilt (1 for keys); tilt scales the grounded speed below
+    let raw_dir = raw_input.normalize_or_zero();
+    let tilt = if raw_dir == Vec2::ZERO { 1.0 } else { raw_input.length().min(1.0) };
+
+    // crouch on hold; only stand back up once there's headroom
+    if input_live && input.crouch {
+        st.is_crouching = true;
+    } else if st.is_crouching && !st.crouch_blocked {
+        st.is_crouching = false;
+    }
+
+    // sprint: held, or flipped by each tap in toggle mode
+    let sprint_held = input.sprint && input_live;
+    if st.sprint_mode == SprintMode::Toggle && sprint_held && !st.sprint_was_held {
+        st.sprint_toggled = !st.sprint_toggled;
+    }
+    st.sprint_was_held = sprint_held;
+    let sprint_input = match st.sprint_mode {
+        SprintMode::Hold => sprint_held,
+        SprintMode::Toggle => st.sprint_toggled,
+    };
+
+    // coyote time: refilled while grounded, counts down once we walk off an edge
+    if st.is_falling {
+        st.coyote_timer = (st.coyote_timer - dt).max(0.0);
+    } else {
+        st.coyote_timer = st.coyote_time;
+    }
+
+    // JUMP: launch upward and go straight into the falling integration below
+    st.jump_buffer_timer = (st.jump_buffer_timer - dt).max(0.0);
+    let jump = input.jump && input_live;
+    // hard turns handle their own jump (see below)
+    let grounded_enough = !st.is_falling || (!st.is_jumping && st.coyote_timer > 0.0);
+    let can_jump = (grounded_enough || !st.jump_requires_ground) && !st.hard_turn_active;
+    if jump && !can_jump {
+        // buffered: fires on the first grounded tick while still fresh
+        st.jump_buffer_timer = st.jump_buffer_time;
+    }
+    if can_jump && (jump || st.jump_buffer_timer > 0.0) {
+        start_jump(&mut st);
+    }
+
+    // DASH: grounded only, a press in the air is dropped
+    st.dash_cooldown_timer = (st.dash_cooldown_timer - dt).max(0.0);
+    let dash = input.dash && input_live;
+
+    // double-tap dash goes the tapped way (taps are tracked in the air too)
+    st.tap_clock += dt;
+    let tap_dash = double_tap_dir(&mut st, raw_input)
+        .map(|d| to_control_frame(d, st.control_frame, st.camera_yaw));
 
     // ✅ FALLING MODE:
-    // - no new horizontal accel forces
+    // - no new horizontal accel forces (unless air_control > 0)
     // - smoothly decay existing horizontal speed to 0
     // - integrate vertical fall velocity with gravity
     if st.is_falling {
         st.pressed = "Falling".to_string();
 
-        // horizontal decay
-        st.speed = (st.speed - st.fall_decel * dt).max(0.0);
+        let air_dir = to_control_frame(raw_dir, st.control_frame, st.camera_yaw);
+        let hold_at_terminal = st.preserve_horizontal_at_terminal && st.fall_vel_y <= max_fall_speed;
+
+        if st.air_control > 0.0 && air_dir != Vec2::ZERO {
+            // air control: ease velocity toward input at max_speed
+            let k = (st.air_control.min(1.0) * st.accel_k * dt).min(1.0);
+            let current = st.dir.normalize_or_zero() * st.speed;
+            let v = current + (air_dir * st.max_speed - current) * k;
+            st.speed = v.length();
+            if st.speed > 0.0 {
+                st.dir = v / st.speed;
+            }
+        } else if !st.is_jumping && st.air_time >= st.fall_preserve_time && !hold_at_terminal {
+            // horizontal decay (after the momentum-preserving grace window;
+            // jumps keep their take-off speed)
+            let fall_decel = if raw_dir != Vec2::ZERO {
+                st.fall_decel_input
+            } else {
+                st.fall_decel_idle
+            };
+            st.speed = (st.speed - fall_decel * dt).max(0.0);
+        }
 
         if st.speed <= st.stop_epsilon {
             st.speed = 0.0;
@@ -145,11 +964,15 @@ pub fn movement_system(
         }
 
         // vertical accelerate down
-        st.fall_vel_y += st.gravity * dt;
+        let ramp = (1.0 + st.gravity_ramp_per_s * st.air_time).min(st.gravity_ramp_max.max(1.0));
+        st.fall_vel_y += st.gravity * ramp * dt;
         if st.fall_vel_y < max_fall_speed {
             st.fall_vel_y = max_fall_speed;
         }
 
+        st.fall_intensity = (st.fall_vel_y / max_fall_speed).clamp(0.0, 1.0);
+        st.footstep_rate = 0.0;
+
         // prevent other logic while falling
         st.accelerating = false;
         st.t = 0.0;
@@ -157,6 +980,7 @@ pub fn movement_system(
         st.hard_turn_active = false;
         st.hard_turn_timer = 0.0;
         st.pending_dir = Vec2::ZERO;
+        st.dash_timer = 0.0;
 
         return;
     }
@@ -164,34 +988,127 @@ pub fn movement_system(
     // ✅ GROUNDED MODE:
     // reset vertical fall speed
     st.fall_vel_y = 0.0;
+    st.is_jumping = false;
+    st.fall_intensity = 0.0;
+    st.footstep_rate = 0.0; // hard-turn pivots hold speed at 0
 
     // ---------------------------
     // NORMAL MODE (your original logic)
     // ---------------------------
-    let desired_dir = read_input_dir(&keys);
+    let input_dir = to_control_frame(raw_dir, st.control_frame, st.camera_yaw);
+    let mut desired_dir = input_dir;
+
+    // auto-run: synthesize input along `dir`, a backward input cancels it
+    if st.auto_run {
+        if desired_dir.dot(st.dir) < 0.0 {
+            st.auto_run = false;
+        } else if desired_dir == Vec2::ZERO {
+            desired_dir = st.dir.normalize_or_zero();
+        }
+    }
+
+    if st.slope_turn_assist {
+        desired_dir = slope_assist_dir(desired_dir, st.ground_normal, st.slope_turn_assist_angle);
+    }
+
+    // scripted move stands in for input (zero target = let go and brake)
+    if let Some(s) = script {
+        desired_dir = if s.target_speed > 0.0 { s.dir.normalize_or_zero() } else { Vec2::ZERO };
+    }
+
     let has_input = desired_dir != Vec2::ZERO;
 
-    st.pressed = direction_string(desired_dir);
+    // a toggled sprint ends once the player stops giving input
+    if !has_input {
+        st.sprint_toggled = false;
+    }
+
+    // sprint / crouch: change the top speed the accel curve heads for
+    // (Shift + input, toward the facing with sprint_forward_only; crouching overrides it)
+    let facing = to_control_frame(Vec2::Y, st.control_frame, st.camera_yaw);
+    let sprint_dir_ok =
+        input_dir != Vec2::ZERO && (!st.sprint_forward_only || input_dir.dot(facing) > 0.0);
+    let sprinting = sprint_input && sprint_dir_ok && !st.is_crouching;
+    st.is_sprinting = sprinting;
+    let top_speed = if let Some(s) = script {
+        s.target_speed.max(0.0)
+    } else if st.is_crouching {
+        st.crouch_speed.min(st.base_max_speed)
+    } else if sprinting {
+        st.base_max_speed * st.sprint_multiplier.max(1.0)
+    } else {
+        st.base_max_speed
+    };
+    if top_speed != st.max_speed {
+        st.max_speed = top_speed;
+        if st.accelerating {
+            retime_accel_curve(&mut st);
+        }
+    }
+
+    st.pressed = if script.is_some() {
+        "Scripted".to_string()
+    } else if st.auto_run && raw_dir == Vec2::ZERO {
+        "Auto-run".to_string()
+    } else if sprinting {
+        format!("Sprinting {}", direction_string(raw_dir))
+    } else if st.is_crouching {
+        format!("Crouching {}", direction_string(raw_dir))
+    } else {
+        direction_string(raw_dir)
+    };
+
+    let dash_ready = !st.hard_turn_active && st.dash_timer <= 0.0 && st.dash_cooldown_timer <= 0.0;
+    if (dash || tap_dash.is_some()) && dash_ready {
+        if let Some(dir) = tap_dash {
+            st.dir = dir;
+        }
+        st.dash_timer = st.dash_duration;
+        st.dash_cooldown_timer = st.dash_cooldown;
+    }
+
+    // dashing: straight line at dash_speed, no turning
+    if st.dash_timer > 0.0 {
+        st.pressed = "Dashing".to_string();
+        st.dash_timer -= dt;
+
+        st.speed = st.dash_speed;
+        st.velocity = st.dir.normalize_or_zero() * st.speed;
+        st.footstep_rate = st.speed / st.max_speed.max(0.001);
+
+        if st.dash_timer <= 0.0 {
+            // hand off to the curves: overspeed eases down with input, decel without
+            st.dash_timer = 0.0;
+            st.accelerating = has_input;
+            st.t = 0.0;
+            st.start_speed = st.speed;
+        }
+        return;
+    }
 
     let moving = st.speed > st.stop_epsilon;
     let current_dir = if moving { st.dir.normalize_or_zero() } else { Vec2::ZERO };
 
     if st.hard_turn_active {
         if !has_input {
+            st.reset_motion();
+            return;
+        }
+
+        st.pending_dir = desired_dir;
+
+        // jump escape: skip the rest of the pivot and launch toward pending_dir
+        if st.jump_cancels_hard_turn && (jump || st.jump_buffer_timer > 0.0) {
             st.hard_turn_active = false;
             st.hard_turn_timer = 0.0;
-            st.pending_dir = Vec2::ZERO;
 
-            st.speed = 0.0;
-            st.velocity = Vec2::ZERO;
-
-            st.accelerating = false;
-            st.t = 0.0;
-            st.start_speed = 0.0;
+            st.dir = st.pending_dir;
+            st.speed = st.max_speed * st.launch_speed_fraction.clamp(0.0, 0.99);
+            st.velocity = st.dir * st.speed;
+            start_jump(&mut st);
             return;
         }
 
-        st.pending_dir = desired_dir;
         st.hard_turn_timer += dt;
 
         st.speed = 0.0;
@@ -212,17 +1129,14 @@ pub fn movement_system(
         let dot = current_dir.dot(desired_dir);
 
         if dot <= st.hard_turn_dot {
-            st.speed = 0.0;
-            st.velocity = Vec2::ZERO;
-
-            st.accelerating = false;
-            st.t = 0.0;
-            st.start_speed = 0.0;
+            // slow reversals keep their momentum and just snap direction below
+            if st.speed >= st.hard_turn_min_speed {
+                st.reset_motion();
 
-            st.hard_turn_active = true;
-            st.hard_turn_timer = 0.0;
-            st.pending_dir = desired_dir;
-            return;
+                st.hard_turn_active = true;
+                st.pending_dir = desired_dir;
+                return;
+            }
         } else if dot <= st.soft_turn_dot {
             soft_turn = true;
         }
@@ -233,9 +1147,18 @@ pub fn movement_system(
     }
 
     restart_curve(&mut st, has_input);
+
+    // launch kick: start the accel curve part-way up when leaving a full stop
+    if has_input && !moving && st.t == 0.0 {
+        let f = st.launch_speed_fraction.clamp(0.0, 0.99);
+        st.t = -(1.0 - f).ln() / st.accel_k.max(0.001);
+    }
     st.t += dt;
 
-    let mut speed = if st.accelerating {
+    let mut speed = if st.accelerating && st.start_speed > st.max_speed {
+        // overspeed (boosts): ease back down to max_speed even while holding input
+        st.max_speed + (st.start_speed - st.max_speed) * inv_square(st.t, st.decel_a)
+    } else if st.accelerating {
         st.max_speed * accel_exp(st.t, st.accel_k).clamp(0.0, 1.0)
     } else {
         st.start_speed * inv_square(st.t, st.decel_a)
@@ -245,12 +1168,95 @@ pub fn movement_system(
         speed *= st.soft_turn_speed_factor;
     }
 
+    // partial stick tilt walks, full tilt runs
+    speed *= tilt;
+
+    // backward cap: limit the part of dir that points against forward
+    let forward = to_control_frame(Vec2::Y, st.control_frame, st.camera_yaw);
+    let back = -st.dir.normalize_or_zero().dot(forward);
+    if back > 0.0 {
+        speed = speed.min(st.max_back_speed / back);
+    }
+
     if !has_input && speed < st.stop_epsilon {
         speed = 0.0;
     }
 
+    // clamp the gain regardless of curve math (braking stays instant)
+    speed = speed.min(st.speed + st.max_accel_per_s * dt);
+
     st.speed = speed;
     st.velocity = if speed > 0.0 { st.dir * speed } else { Vec2::ZERO };
+    st.footstep_rate = speed / st.max_speed.max(0.001);
+}
+
+// On WASM the canvas can lose focus without key-up events reaching Bevy,
+// leaving keys stuck in ButtonInput; drop them and any input-driven state.
+#[cfg(target_arch = "wasm32")]
+pub fn release_input_on_focus_lost(
+    mut focus: EventReader<bevy::window::WindowFocused>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut st: ResMut<MovementState>
+) {
+    if !focus.read().any(|e| !e.focused) {
+        return;
+    }
+
+    keys.reset_all();
+    release_held_input(&mut st);
+}
+
+// A stick that was held when its pad disconnected reads as zero from now on (bevy drops
+// its axes); also drop auto-run/pivots so the player just coasts to a stop.
+pub fn release_input_on_gamepad_disconnect(
+    mut connections: EventReader<bevy::input::gamepad::GamepadConnectionEvent>,
+    mut st: ResMut<MovementState>
+) {
+    if connections.read().any(|e| e.disconnected()) {
+        release_held_input(&mut st);
+    }
+}
+
+fn release_held_input(st: &mut MovementState) {
+    st.pressed = "Idle".to_string();
+    st.auto_run = false;
+    st.sprint_toggled = false;
+    st.hard_turn_active = false;
+    st.hard_turn_timer = 0.0;
+    st.pending_dir = Vec2::ZERO;
+}
+
+// FixedUpdate may skip a frame, so a just_pressed read there can be missed;
+// latch the press here and let movement_system consume it.
+pub fn latch_jump_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<Keybindings>,
+    mut st: ResMut<MovementState>
+) {
+    if bindings.just_pressed(&keys, InputAction::Jump) {
+        st.press_jump();
+    }
+}
+
+pub fn latch_dash_input(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<Keybindings>,
+    mut st: ResMut<MovementState>
+) {
+    if bindings.just_pressed(&keys, InputAction::Dash) {
+        st.dash_pressed = true;
+    }
+}
+
+// Runs in Update so a single key press toggles exactly once.
+pub fn toggle_auto_run(
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<Keybindings>,
+    mut st: ResMut<MovementState>
+) {
+    if bindings.just_pressed(&keys, InputAction::AutoRun) {
+        st.auto_run = !st.auto_run;
+    }
 }
 
 fn direction_string(dir: Vec2) -> String {
@@ -272,3 +1278,762 @@ fn direction_string(dir: Vec2) -> String {
     }
     parts.join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    const DT: f32 = 1.0 / 64.0;
+
+    fn fixed_time() -> Time {
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(DT));
+        time
+    }
+
+    fn world_with(st: MovementState) -> World {
+        let mut world = World::new();
+        world.insert_resource(fixed_time());
+        world.insert_resource(st);
+        world.insert_resource(InputFrame::default());
+        world.init_resource::<Events<ScriptedMove>>();
+        world
+    }
+
+    fn tick(world: &mut World, input: InputFrame) -> &MovementState {
+        *world.resource_mut::<InputFrame>() = input;
+        world.run_system_once(movement_system);
+        world.resource::<MovementState>()
+    }
+
+    fn walk(move_dir: Vec2) -> InputFrame {
+        InputFrame { move_dir, ..default() }
+    }
+
+    fn jump() -> InputFrame {
+        InputFrame { jump: true, ..default() }
+    }
+
+    // mid-fall, well past the coyote window
+    fn airborne() -> MovementState {
+        MovementState {
+            is_falling: true,
+            is_jumping: true,
+            fall_vel_y: -5.0,
+            coyote_timer: 0.0,
+            ..default()
+        }
+    }
+
+    #[test]
+    fn jump_without_ground_requirement_fires_mid_air() {
+        let mut world = world_with(MovementState { jump_requires_ground: false, ..airborne() });
+        let st = tick(&mut world, jump());
+        assert!(st.fall_vel_y > 0.0);
+
+        // and again on the next press
+        world.resource_mut::<MovementState>().fall_vel_y = -5.0;
+        let st = tick(&mut world, jump());
+        assert!(st.fall_vel_y > 0.0);
+    }
+
+    #[test]
+    fn jump_requiring_ground_is_buffered_mid_air() {
+        let mut world = world_with(airborne());
+        let st = tick(&mut world, jump());
+        assert!(st.fall_vel_y < -5.0);
+        assert!(st.jump_buffer_timer > 0.0);
+    }
+
+    // grounded for a tick, then walked off an edge and fell for `ticks` ticks
+    fn walked_off_edge(ticks: u32) -> World {
+        let mut world = world_with(MovementState { is_falling: false, ..default() });
+        tick(&mut world, InputFrame::default());
+        world.resource_mut::<MovementState>().is_falling = true;
+        for _ in 0..ticks {
+            tick(&mut world, InputFrame::default());
+        }
+        world
+    }
+
+    #[test]
+    fn jump_just_after_leaving_an_edge_still_fires() {
+        let mut world = walked_off_edge(2);
+        assert!(world.resource::<MovementState>().coyote_timer > 0.0);
+        let st = tick(&mut world, jump());
+        assert!(st.is_jumping);
+        assert!(st.fall_vel_y > 0.0);
+    }
+
+    #[test]
+    fn jump_after_the_coyote_window_is_buffered() {
+        let coyote_ticks = (MovementState::default().coyote_time / DT).ceil() as u32;
+        let mut world = walked_off_edge(coyote_ticks + 1);
+        assert_eq!(world.resource::<MovementState>().coyote_timer, 0.0);
+        let st = tick(&mut world, jump());
+        assert!(!st.is_jumping);
+        assert!(st.fall_vel_y <= 0.0);
+        assert!(st.jump_buffer_timer > 0.0);
+    }
+
+    // sprint held with `move_dir` for a second
+    fn sprint_toward(forward_only: bool, move_dir: Vec2) -> MovementState {
+        let mut world = world_with(MovementState {
+            sprint_forward_only: forward_only,
+            // out of the way, so only the sprint rule limits backpedaling
+            max_back_speed: 100.0,
+            ..default()
+        });
+        for _ in 0..64 {
+            tick(&mut world, InputFrame { sprint: true, ..walk(move_dir) });
+        }
+        world.remove_resource::<MovementState>().unwrap()
+    }
+
+    #[test]
+    fn forward_only_sprint_does_not_boost_backpedaling() {
+        let st = sprint_toward(true, -Vec2::Y);
+        assert!(!st.is_sprinting);
+        assert!(st.speed <= st.base_max_speed);
+
+        let st = sprint_toward(true, Vec2::Y);
+        assert!(st.is_sprinting);
+        assert!(st.speed > st.base_max_speed);
+    }
+
+    #[test]
+    fn sprint_in_any_direction_boosts_backpedaling() {
+        let st = sprint_toward(false, -Vec2::Y);
+        assert!(st.is_sprinting);
+        assert!(st.speed > st.base_max_speed);
+    }
+
+    // D held for `hold` ticks, released for `gap` ticks, then pressed again
+    fn double_tap_right(world: &mut World, hold: usize, gap: usize) {
+        for _ in 0..hold {
+            tick(world, walk(Vec2::X));
+        }
+        for _ in 0..gap {
+            tick(world, InputFrame::default());
+        }
+        tick(world, walk(Vec2::X));
+    }
+
+    #[test]
+    fn auto_run_keeps_moving_without_keys() {
+        let mut world = world_with(MovementState { auto_run: true, dir: Vec2::X, ..default() });
+        let mut last = 0.0;
+        for _ in 0..64 {
+            let st = tick(&mut world, InputFrame::default());
+            assert!(st.speed > last || st.speed == st.max_speed);
+            last = st.speed;
+        }
+        let st = world.resource::<MovementState>();
+        assert!(st.auto_run);
+        assert!(st.velocity.normalize().abs_diff_eq(Vec2::X, 1e-6));
+
+        // pulling back cancels it
+        let st = tick(&mut world, walk(Vec2::NEG_X));
+        assert!(!st.auto_run);
+    }
+
+    fn first_moving_tick_speed(launch_speed_fraction: f32) -> f32 {
+        let mut world = world_with(MovementState { launch_speed_fraction, ..default() });
+        tick(&mut world, InputFrame::default());
+        tick(&mut world, walk(Vec2::X)).speed
+    }
+
+    #[test]
+    fn launch_kick_moves_on_the_first_tick() {
+        let kicked = first_moving_tick_speed(0.5);
+        assert!(kicked > 0.0);
+        assert!(kicked > first_moving_tick_speed(0.0));
+    }
+
+    #[test]
+    fn dt_spike_gains_at_most_max_accel() {
+        let mut world = world_with(MovementState { max_accel_per_s: 4.0, ..default() });
+        for _ in 0..4 {
+            tick(&mut world, walk(Vec2::X));
+        }
+        let before = world.resource::<MovementState>().speed;
+
+        // a half-second hitch: the curve alone would jump most of the way to max_speed
+        let spike = 0.5;
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(spike));
+        world.insert_resource(time);
+        let st = tick(&mut world, walk(Vec2::X));
+        assert!(st.max_speed - before > st.max_accel_per_s * spike);
+        assert!((st.speed - before - st.max_accel_per_s * spike).abs() < 1e-4, "{} -> {}", before, st.speed);
+    }
+
+    #[test]
+    fn reset_motion_stops_but_keeps_tuning() {
+        let mut st = MovementState {
+            max_speed: 9.0,
+            accel_k: 3.0,
+            dash_speed: 25.0,
+            dir: Vec2::X,
+            velocity: Vec2::new(5.0, 0.0),
+            speed: 5.0,
+            fall_vel_y: -3.0,
+            dash_timer: 0.1,
+            jump_buffer_timer: 0.05,
+            hard_turn_active: true,
+            hard_turn_timer: 0.02,
+            pending_dir: Vec2::NEG_X,
+            accelerating: true,
+            t: 0.4,
+            start_speed: 2.0,
+            ..default()
+        };
+        st.reset_motion();
+
+        assert_eq!(st.velocity, Vec2::ZERO);
+        assert_eq!(st.speed, 0.0);
+        assert_eq!(st.fall_vel_y, 0.0);
+        assert_eq!(st.dash_timer, 0.0);
+        assert_eq!(st.jump_buffer_timer, 0.0);
+        assert!(!st.hard_turn_active);
+        assert_eq!(st.hard_turn_timer, 0.0);
+        assert_eq!(st.pending_dir, Vec2::ZERO);
+        assert!(!st.accelerating);
+        assert_eq!((st.t, st.start_speed), (0.0, 0.0));
+
+        assert_eq!((st.max_speed, st.accel_k, st.dash_speed), (9.0, 3.0, 25.0));
+        assert_eq!(st.dir, Vec2::X);
+    }
+
+    #[test]
+    fn diff_lists_the_fields_a_tick_changed() {
+        let mut world = world_with(MovementState { is_falling: false, ..default() });
+        tick(&mut world, InputFrame::default());
+        let before = world.resource::<MovementState>().snapshot();
+
+        let st = tick(&mut world, walk(Vec2::X));
+        assert_eq!(st.diff(&before), ["dir", "velocity", "speed", "footstep_rate"]);
+        assert!(st.snapshot().diff(&st.snapshot()).is_empty());
+    }
+
+    #[test]
+    fn scripted_move_drives_toward_its_target_speed() {
+        let mut world = world_with(MovementState::default());
+        world.send_event(ScriptedMove { dir: Vec2::X, target_speed: 3.0, duration: 1.0 });
+
+        // no keys for the first half, keys pulling the other way for the rest: both ignored
+        let mut last = 0.0;
+        for i in 0..63 {
+            let input = if i < 32 { InputFrame::default() } else { walk(Vec2::NEG_Y) };
+            tick(&mut world, input);
+            // each run_system_once reads the queued event anew; a real reader sees it once
+            world.resource_mut::<Events<ScriptedMove>>().clear();
+            let st = world.resource::<MovementState>();
+            assert!(st.speed >= last && st.speed <= 3.0, "{last} -> {}", st.speed);
+            assert!(st.velocity.normalize().abs_diff_eq(Vec2::X, 1e-6));
+            last = st.speed;
+        }
+        assert!(last > 2.9, "{last}");
+
+        // over: no input, so it brakes
+        tick(&mut world, InputFrame::default());
+        let st = tick(&mut world, InputFrame::default());
+        assert!(st.scripted.is_none());
+        assert!(st.speed < last);
+    }
+
+    #[test]
+    fn dash_hands_back_to_the_speed_curve() {
+        let grounded = || MovementState { is_falling: false, ..default() };
+        let mut dashed = world_with(grounded());
+        let mut walked = world_with(grounded());
+        for _ in 0..64 {
+            tick(&mut dashed, walk(Vec2::Y));
+            tick(&mut walked, walk(Vec2::Y));
+        }
+
+        let st = tick(&mut dashed, InputFrame { dash: true, ..walk(Vec2::Y) });
+        assert_eq!(st.speed, st.dash_speed);
+        let dash_ticks = (st.dash_duration / DT).ceil() as u32;
+        for _ in 0..dash_ticks {
+            tick(&mut dashed, walk(Vec2::Y));
+        }
+        let st = dashed.resource::<MovementState>();
+        assert_eq!(st.dash_timer, 0.0);
+        assert!(st.speed < st.dash_speed);
+
+        // overspeed eases down to the same max speed as a plain walk
+        let mut last = st.speed;
+        for _ in 0..256 {
+            let speed = tick(&mut dashed, walk(Vec2::Y)).speed;
+            assert!(speed <= last);
+            last = speed;
+            tick(&mut walked, walk(Vec2::Y));
+        }
+        let st = dashed.resource::<MovementState>();
+        let plain = walked.resource::<MovementState>();
+        assert!((st.speed - plain.speed).abs() < 0.05, "{} vs {}", st.speed, plain.speed);
+        assert!(st.velocity.distance(plain.velocity) < 0.05);
+    }
+
+    #[test]
+    fn quick_double_tap_dashes_that_way() {
+        let mut world = world_with(MovementState { double_tap_dash: true, ..default() });
+        double_tap_right(&mut world, 3, 5);
+
+        let st = world.resource::<MovementState>();
+        assert!(st.dash_timer > 0.0);
+        assert_eq!(st.dir, Vec2::X);
+        assert_eq!(st.speed, st.dash_speed);
+    }
+
+    #[test]
+    fn slow_double_tap_does_not_dash() {
+        let mut world = world_with(MovementState { double_tap_dash: true, ..default() });
+        // 40 ticks = 0.625 s, past the 0.25 s window
+        double_tap_right(&mut world, 3, 40);
+
+        let st = world.resource::<MovementState>();
+        assert_eq!(st.dash_timer, 0.0);
+        assert!(st.speed < st.max_speed);
+    }
+
+    #[test]
+    fn world_frame_ignores_the_camera_yaw() {
+        let velocity_at = |camera_yaw| {
+            let mut world = world_with(MovementState { camera_yaw, ..default() });
+            assert_eq!(world.resource::<MovementState>().control_frame, ControlFrame::World);
+            for _ in 0..16 {
+                tick(&mut world, walk(Vec2::new(0.3, 1.0)));
+            }
+            world.resource::<MovementState>().velocity
+        };
+
+        let straight = velocity_at(0.0);
+        assert!(straight.x > 0.0 && straight.y > straight.x);
+        for yaw in [0.7, 2.0, -2.5] {
+            assert_eq!(velocity_at(yaw), straight);
+        }
+    }
+
+    #[test]
+    fn forward_follows_the_camera_yaw() {
+        use std::f32::consts::FRAC_PI_2;
+
+        let w = to_control_frame(Vec2::Y, ControlFrame::Camera, FRAC_PI_2);
+        assert!(w.distance(Vec2::X) < 1e-6, "{w}");
+        // D is to the right of +X, i.e. +Z
+        let d = to_control_frame(Vec2::X, ControlFrame::Camera, FRAC_PI_2);
+        assert!(d.distance(Vec2::Y) < 1e-6, "{d}");
+
+        let w = to_control_frame(Vec2::Y, ControlFrame::Camera, 0.0);
+        assert!(w.distance(-Vec2::Y) < 1e-6, "{w}");
+
+        // a camera placed behind a facing pushes W along it
+        for facing in [Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::new(0.6, -0.8)] {
+            let yaw = crate::systems::camera_yaw_behind(facing);
+            let w = to_control_frame(Vec2::Y, ControlFrame::Camera, yaw);
+            assert!(w.distance(facing) < 1e-5, "{facing}: {w}");
+        }
+    }
+
+    #[test]
+    fn replaying_input_frames_is_bit_identical() {
+        let mut frames = Vec::new();
+        frames.extend(std::iter::repeat_n(walk(Vec2::Y), 40));
+        frames.extend(std::iter::repeat_n(InputFrame { sprint: true, ..walk(Vec2::Y) }, 20));
+        frames.push(InputFrame { dash: true, ..walk(Vec2::new(0.7, 0.7)) });
+        frames.extend(std::iter::repeat_n(walk(Vec2::new(0.7, 0.7)), 20));
+        frames.extend(std::iter::repeat_n(walk(-Vec2::Y), 30));
+        frames.push(InputFrame { jump: true, ..walk(Vec2::X) });
+        frames.extend(std::iter::repeat_n(InputFrame::default(), 30));
+
+        let run = || {
+            let mut world = world_with(MovementState::default());
+            let mut pos = Vec2::ZERO;
+            for frame in &frames {
+                let st = tick(&mut world, *frame);
+                pos += st.velocity * DT;
+            }
+            (world.resource::<MovementState>().debug_dump(), pos)
+        };
+
+        let (dump_a, pos_a) = run();
+        let (dump_b, pos_b) = run();
+
+        // {:?} prints floats round-trip exact (and -0.0 apart from 0.0), covering the
+        // private timers too
+        assert_eq!(dump_a, dump_b);
+        assert_eq!(pos_a.x.to_bits(), pos_b.x.to_bits());
+        assert_eq!(pos_a.y.to_bits(), pos_b.y.to_bits());
+        assert!(pos_a != Vec2::ZERO);
+    }
+
+    // distance walked in one second of ticks of `dt`: accelerating, then letting go
+    fn distance_in_a_second(dt: f32) -> f32 {
+        let mut world = world_with(MovementState::default());
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(dt));
+        world.insert_resource(time);
+
+        let ticks = (1.0 / dt).round() as u32;
+        let mut distance = 0.0;
+        for i in 0..ticks {
+            let input = if i < ticks / 2 { walk(Vec2::Y) } else { InputFrame::default() };
+            distance += tick(&mut world, input).velocity.length() * dt;
+        }
+        distance
+    }
+
+    #[test]
+    fn distance_does_not_depend_on_the_timestep() {
+        let coarse = distance_in_a_second(1.0 / 32.0);
+        let fine = distance_in_a_second(1.0 / 128.0);
+        assert!(coarse > 1.0);
+        assert!((coarse - fine).abs() / fine < 0.05, "{coarse} vs {fine}");
+    }
+
+    #[test]
+    fn captured_keys_move_like_the_equivalent_frames() {
+        use KeyCode::{ KeyD, KeyW, ShiftLeft };
+        let diagonal = Vec2::ONE.normalize();
+        // (held keys, jump pressed, the frame they should capture to)
+        let mut script = Vec::new();
+        script.extend(std::iter::repeat_n((vec![KeyW, KeyD], false, walk(diagonal)), 30));
+        let sprint = InputFrame { sprint: true, ..walk(diagonal) };
+        script.extend(std::iter::repeat_n((vec![KeyW, KeyD, ShiftLeft], false, sprint), 10));
+        script.push((vec![KeyW], true, InputFrame { jump: true, ..walk(Vec2::Y) }));
+        script.extend(std::iter::repeat_n((vec![], false, InputFrame::default()), 30));
+
+        let mut live = input_world(Keybindings::default());
+        live.insert_resource(fixed_time());
+        live.init_resource::<Events<ScriptedMove>>();
+        let mut synthetic = world_with(MovementState::default());
+
+        for (keys, jump, frame) in script {
+            if jump {
+                live.resource_mut::<MovementState>().press_jump();
+            }
+            assert_eq!(capture(&mut live, &keys), frame);
+            live.run_system_once(movement_system);
+
+            let expected = tick(&mut synthetic, frame).snapshot();
+            assert_eq!(live.resource::<MovementState>().snapshot(), expected);
+        }
+        assert!(synthetic.resource::<MovementState>().fall_vel_y != 0.0);
+    }
+
+    fn input_world(bindings: Keybindings) -> World {
+        let mut world = World::new();
+        world.insert_resource(bindings);
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<VirtualJoystick>();
+        world.init_resource::<Gamepads>();
+        world.init_resource::<Axis<GamepadAxis>>();
+        world.init_resource::<InputFrame>();
+        world.init_resource::<MovementState>();
+        world
+    }
+
+    // holds exactly `keys` this frame and captures the InputFrame
+    fn capture(world: &mut World, keys: &[KeyCode]) -> InputFrame {
+        let mut input = world.resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        input.clear();
+        for &key in keys {
+            input.press(key);
+        }
+        world.run_system_once(capture_input_frame);
+        *world.resource::<InputFrame>()
+    }
+
+    #[test]
+    fn rebound_forward_key_drives_move_dir() {
+        let mut world = input_world(Keybindings { forward: KeyCode::ArrowUp, ..default() });
+        assert_eq!(capture(&mut world, &[KeyCode::ArrowUp]).move_dir, Vec2::Y);
+        // the old key no longer does anything
+        assert_eq!(capture(&mut world, &[KeyCode::KeyW]).move_dir, Vec2::ZERO);
+        assert_eq!(capture(&mut world, &[KeyCode::ArrowUp, KeyCode::KeyD]).move_dir, Vec2::ONE.normalize());
+    }
+
+    // W, then S pressed on top of it
+    fn forward_then_back(rule: OpposingInputRule) -> Vec2 {
+        let mut world = input_world(Keybindings::default());
+        world.resource_mut::<MovementState>().opposing_input = rule;
+        capture(&mut world, &[KeyCode::KeyW]);
+        capture(&mut world, &[KeyCode::KeyW, KeyCode::KeyS]).move_dir
+    }
+
+    #[test]
+    fn opposing_keys_follow_the_input_rule() {
+        assert_eq!(forward_then_back(OpposingInputRule::LastWins), Vec2::NEG_Y);
+        assert_eq!(forward_then_back(OpposingInputRule::Cancel).y, 0.0);
+    }
+
+    #[test]
+    fn debug_dump_lists_public_fields() {
+        let st = MovementState { max_speed: 7.5, is_falling: true, ..default() };
+        let dump = st.debug_dump();
+        assert!(dump.contains("max_speed: 7.5"), "{dump}");
+        assert!(dump.contains("is_falling: true"), "{dump}");
+    }
+
+    // speed after holding `move_dir` for two seconds
+    fn settled_speed(st: MovementState, move_dir: Vec2) -> f32 {
+        let mut world = world_with(st);
+        for _ in 0..128 {
+            tick(&mut world, walk(move_dir));
+        }
+        world.resource::<MovementState>().speed
+    }
+
+    #[test]
+    fn backpedaling_settles_at_max_back_speed() {
+        let st = || MovementState { max_back_speed: 3.0, ..default() };
+        assert!((settled_speed(st(), -Vec2::Y) - 3.0).abs() < 1e-3);
+        assert!((settled_speed(st(), Vec2::Y) - st().max_speed).abs() < 1e-3);
+    }
+
+    #[test]
+    fn toggled_sprint_lasts_until_tapped_again_or_stopped() {
+        let mut world = world_with(MovementState { sprint_mode: SprintMode::Toggle, ..default() });
+        let tap = InputFrame { sprint: true, ..walk(Vec2::Y) };
+
+        tick(&mut world, tap);
+        for _ in 0..32 {
+            assert!(tick(&mut world, walk(Vec2::Y)).is_sprinting);
+        }
+
+        // a second tap turns it off
+        tick(&mut world, tap);
+        assert!(!tick(&mut world, walk(Vec2::Y)).is_sprinting);
+
+        // back on, then letting go of the direction ends it
+        tick(&mut world, walk(Vec2::Y));
+        tick(&mut world, tap);
+        assert!(tick(&mut world, walk(Vec2::Y)).sprint_toggled);
+        assert!(!tick(&mut world, InputFrame::default()).sprint_toggled);
+        assert!(!tick(&mut world, walk(Vec2::Y)).is_sprinting);
+    }
+
+    // facing after pushing diagonally into a 40 degree face rising toward +X
+    fn pushing_into_a_steep_face(assist: bool) -> Vec2 {
+        let slope = 40f32.to_radians();
+        let mut world = world_with(MovementState {
+            slope_turn_assist: assist,
+            ground_normal: Vec3::new(-slope.sin(), slope.cos(), 0.0),
+            ..default()
+        });
+        for _ in 0..8 {
+            tick(&mut world, walk(Vec2::ONE.normalize()));
+        }
+        world.resource::<MovementState>().dir
+    }
+
+    #[test]
+    fn slope_turn_assist_walks_along_a_steep_face() {
+        assert!(pushing_into_a_steep_face(true).distance(Vec2::Y) < 1e-5);
+        assert!(pushing_into_a_steep_face(false).distance(Vec2::ONE.normalize()) < 1e-5);
+    }
+
+    #[test]
+    fn gamepad_disconnect_releases_held_input() {
+        use bevy::input::gamepad::{ GamepadConnection, GamepadConnectionEvent };
+        let mut world = World::new();
+        world.insert_resource(MovementState {
+            pressed: "Sprinting Forward".to_string(),
+            auto_run: true,
+            sprint_toggled: true,
+            hard_turn_active: true,
+            hard_turn_timer: 0.05,
+            pending_dir: Vec2::NEG_Y,
+            ..default()
+        });
+        world.init_resource::<Events<GamepadConnectionEvent>>();
+        world.send_event(GamepadConnectionEvent::new(Gamepad::new(0), GamepadConnection::Disconnected));
+
+        world.run_system_once(release_input_on_gamepad_disconnect);
+
+        let st = world.resource::<MovementState>();
+        assert_eq!(st.pressed, "Idle");
+        assert!(!st.auto_run && !st.sprint_toggled && !st.is_hard_turning());
+        assert_eq!((st.hard_turn_timer, st.pending_dir), (0.0, Vec2::ZERO));
+    }
+
+    // horizontal speed after walking off an edge at full speed and falling 8 ticks
+    fn speed_after_falling(input: InputFrame) -> f32 {
+        let mut world = world_with(MovementState {
+            is_falling: true,
+            speed: 6.0,
+            dir: Vec2::Y,
+            ..default()
+        });
+        for _ in 0..8 {
+            tick(&mut world, input);
+        }
+        world.resource::<MovementState>().speed
+    }
+
+    #[test]
+    fn steering_mid_fall_decays_slower_than_letting_go() {
+        let st = MovementState::default();
+        let held = speed_after_falling(walk(Vec2::Y));
+        let released = speed_after_falling(InputFrame::default());
+        assert!((held - (6.0 - st.fall_decel_input * 8.0 * DT)).abs() < 1e-4, "{held}");
+        assert!((released - (6.0 - st.fall_decel_idle * 8.0 * DT)).abs() < 1e-4, "{released}");
+        assert!(held > released);
+    }
+
+    #[test]
+    fn fall_intensity_runs_from_apex_to_terminal() {
+        // just past the top of a jump
+        let mut world = world_with(MovementState { fall_vel_y: 0.1, ..airborne() });
+        assert!(tick(&mut world, InputFrame::default()).fall_intensity < 0.05);
+
+        for _ in 0..128 {
+            tick(&mut world, InputFrame::default());
+        }
+        let st = world.resource::<MovementState>();
+        assert_eq!(st.fall_vel_y, -3.0 * st.base_max_speed);
+        assert!((st.fall_intensity - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn gravity_ramp_builds_up_to_its_cap() {
+        let mut world = world_with(MovementState {
+            gravity_ramp_per_s: 1.0,
+            gravity_ramp_max: 2.0,
+            // terminal velocity far out of reach
+            base_max_speed: 100.0,
+            fall_vel_y: 0.0,
+            ..airborne()
+        });
+        let gravity = world.resource::<MovementState>().gravity;
+
+        let mut pulls = Vec::new();
+        for _ in 0..128 {
+            let before = world.resource::<MovementState>().fall_vel_y;
+            let st = tick(&mut world, InputFrame::default());
+            pulls.push((before - st.fall_vel_y) / DT);
+            // player_system counts air time
+            world.resource_mut::<MovementState>().air_time += DT;
+        }
+
+        assert!((pulls[0] + gravity).abs() < 1e-3, "{}", pulls[0]);
+        assert!(pulls.windows(2).all(|w| w[1] >= w[0] - 1e-3));
+        // capped at twice gravity from one second in
+        assert!(pulls[64..].iter().all(|&p| (p + 2.0 * gravity).abs() < 1e-2), "{pulls:?}");
+    }
+
+    #[test]
+    fn footstep_rate_follows_speed_and_stops_in_the_air() {
+        let rate_at = |tilt: f32| {
+            let mut world = world_with(MovementState::default());
+            for _ in 0..128 {
+                tick(&mut world, walk(Vec2::Y * tilt));
+            }
+            world.resource::<MovementState>().footstep_rate
+        };
+        let full = rate_at(1.0);
+        assert!((full - 1.0).abs() < 1e-3, "{full}");
+        assert!((rate_at(0.5) - full / 2.0).abs() < 1e-3);
+
+        let mut world = world_with(MovementState { speed: 6.0, ..airborne() });
+        assert_eq!(tick(&mut world, walk(Vec2::Y)).footstep_rate, 0.0);
+    }
+
+    #[test]
+    fn running_off_a_ledge_keeps_full_speed_for_the_preserve_window() {
+        let mut world = world_with(MovementState {
+            fall_preserve_time: 0.2,
+            speed: 6.0,
+            fall_vel_y: 0.0,
+            air_time: 0.0,
+            is_jumping: false,
+            ..airborne()
+        });
+        let mut speeds = Vec::new();
+        for _ in 0..24 {
+            speeds.push(tick(&mut world, InputFrame::default()).speed);
+            // player_system counts air time
+            world.resource_mut::<MovementState>().air_time += DT;
+        }
+
+        let window = (0.2 / DT).ceil() as usize;
+        assert!(speeds[..window].iter().all(|&s| s == 6.0), "{speeds:?}");
+        assert!(speeds[window..].windows(2).all(|w| w[1] < w[0]), "{speeds:?}");
+    }
+
+    // horizontal speed after 16 ticks falling at terminal velocity
+    fn speed_at_terminal(preserve: bool) -> f32 {
+        let terminal = -3.0 * MovementState::default().base_max_speed;
+        let mut world = world_with(MovementState {
+            preserve_horizontal_at_terminal: preserve,
+            fall_vel_y: terminal,
+            speed: 5.0,
+            is_jumping: false,
+            ..airborne()
+        });
+        for _ in 0..16 {
+            let st = tick(&mut world, InputFrame::default());
+            assert_eq!(st.fall_vel_y, terminal);
+        }
+        world.resource::<MovementState>().speed
+    }
+
+    #[test]
+    fn terminal_velocity_can_freeze_horizontal_speed() {
+        assert_eq!(speed_at_terminal(true), 5.0);
+        assert!(speed_at_terminal(false) < 5.0);
+    }
+
+    // running forward, then reversed into a hard turn and jumped on its first pivot tick
+    fn jump_mid_hard_turn(cancels: bool) -> MovementState {
+        let mut world = world_with(MovementState { jump_cancels_hard_turn: cancels, ..default() });
+        for _ in 0..32 {
+            tick(&mut world, walk(Vec2::Y));
+        }
+        assert!(tick(&mut world, walk(-Vec2::Y)).is_hard_turning());
+        tick(&mut world, InputFrame { jump: true, ..walk(-Vec2::Y) });
+        world.remove_resource::<MovementState>().unwrap()
+    }
+
+    #[test]
+    fn jump_cancels_a_hard_turn_pivot() {
+        let st = jump_mid_hard_turn(true);
+        assert!(!st.is_hard_turning());
+        assert!(st.is_jumping);
+        assert_eq!(st.fall_vel_y, st.jump_impulse);
+        assert_eq!(st.dir, -Vec2::Y);
+
+        // otherwise the press waits for the pivot
+        let st = jump_mid_hard_turn(false);
+        assert!(st.is_hard_turning());
+        assert!(!st.is_jumping);
+        assert!(st.jump_buffer_timer > 0.0);
+    }
+
+    // reversed after walking forward for `ticks`, with pivots only from 3 units/s
+    fn reversed_after(ticks: usize) -> MovementState {
+        let mut world = world_with(MovementState { hard_turn_min_speed: 3.0, ..default() });
+        for _ in 0..ticks {
+            tick(&mut world, walk(Vec2::Y));
+        }
+        tick(&mut world, walk(-Vec2::Y));
+        world.remove_resource::<MovementState>().unwrap()
+    }
+
+    #[test]
+    fn slow_reversal_snaps_without_a_pivot() {
+        let st = reversed_after(2);
+        assert!(!st.is_hard_turning());
+        assert_eq!(st.dir, -Vec2::Y);
+        assert!(st.speed > 0.0);
+
+        let st = reversed_after(64);
+        assert!(st.is_hard_turning());
+        assert_eq!(st.speed, 0.0);
+    }
+}
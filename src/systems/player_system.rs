@@ -1,174 +1,2570 @@
-use bevy::prelude::*;
+use std::any::TypeId;
+
+use bevy::{
+    ecs::world::EntityRef,
+    input::mouse::{ MouseMotion, MouseScrollUnit, MouseWheel },
+    prelude::*,
+    window::{ CursorGrabMode, PrimaryWindow },
+};
 use bevy_rapier3d::prelude::*;
 
-use crate::systems::{ movement_system, Ground, MovementState, SkyboxHandle };
+use crate::systems::{
+    capture_input_frame,
+    input_capture_enabled,
+    movement_system,
+    move_platforms,
+    latch_dash_input,
+    latch_jump_input,
+    level_ready,
+    release_input_on_gamepad_disconnect,
+    BoostPad,
+    toggle_auto_run,
+    Ground,
+    Health,
+    MovementState,
+    MovingPlatform,
+    Wall,
+    ScriptedMove,
+    SkyboxHandle,
+    SkyboxSettings,
+};
 
+// default follow distance (CameraZoom starts here)
 pub const CAMERA_DISTANCE: f32 = 10.0;
-const CAMERA_HEIGHT: f32 = 5.0;
+// pixel-unit scrolling (touchpads, browsers) counts this many pixels as one wheel notch
+const SCROLL_PIXELS_PER_LINE: f32 = 100.0;
+// default elevation of the camera above the player, radians (atan(1/2): 1 up per 2 back)
+const CAMERA_PITCH: f32 = 0.4636;
+
+// gap left between the camera and geometry it's pulled in front of
+const CAMERA_COLLISION_MARGIN: f32 = 0.2;
+
+// distance between the listener's ears, about the player's width
+const LISTENER_EAR_GAP: f32 = 0.8;
+
+// first-person camera height above the player's center
+const FIRST_PERSON_EYE_OFFSET: f32 = 0.35;
+// first-person reticle: how far the aim ray reaches, and the hit marker's size
+const RETICLE_RANGE: f32 = 100.0;
+const RETICLE_MARKER_RADIUS: f32 = 0.15;
+pub const CAMERA_MODE_KEY: KeyCode = KeyCode::KeyV;
+
+// locks/hides the cursor so mouse motion orbits the camera (press again to release)
+pub const CURSOR_CAPTURE_KEY: KeyCode = KeyCode::Tab;
 
 const PLAYER_HALF_HEIGHT: f32 = 0.5;
+const PLAYER_CROUCH_HALF_HEIGHT: f32 = 0.25;
+// capsule collider radius; the capsule spans the full PLAYER_HALF_HEIGHT
+const PLAYER_RADIUS: f32 = 0.45;
 
-// Footprint “sensor” (fall only when whole footprint is off the edge)
-const FOOT_HALF_X: f32 = 0.49;
-const FOOT_HALF_Z: f32 = 0.49;
+// Footprint “sensor” (fall only when whole footprint is off the edge);
+// matches the capsule's width
+const FOOT_HALF_X: f32 = PLAYER_RADIUS;
+const FOOT_HALF_Z: f32 = PLAYER_RADIUS;
 const FOOT_HALF_Y: f32 = 0.03;
 const FOOT_BELOW_FEET: f32 = 0.01;
 
+// gap kept between the player and a wall it slides along
+const WALL_SKIN: f32 = 0.01;
+// slide iterations per move (wall, then corner, then give up)
+const WALL_SLIDE_ITERATIONS: usize = 3;
+
+// How far below the feet the stuck-in-air recovery looks for ground
+const AIR_RECOVERY_REACH: f32 = 0.5;
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<PlayerDimensions>();
+        app.init_resource::<PlayerSpawn>();
+        app.init_resource::<KillPlane>();
+        app.init_resource::<CameraFollowSettings>();
+        app.init_resource::<CameraZoom>();
+        app.init_resource::<CameraMode>();
+        app.init_resource::<ReticleTarget>();
+        app.init_resource::<CameraTrauma>();
+        app.add_event::<CameraShake>();
+        app.init_resource::<CameraRoll>();
+        app.init_resource::<CameraKick>();
+        app.init_resource::<GroundEffects>();
+        app.world_mut().resource_mut::<GroundEffects>().register::<BoostPad>(boost_pad_effect);
+        app.init_resource::<OcclusionFade>();
+        app.init_resource::<PlayerGroundMask>();
+        app.init_resource::<DialogState>();
+        app.init_resource::<PlayerController>();
+        app.init_resource::<ListenerMode>();
+        app.add_event::<AirtimeStats>();
+        app.add_event::<Landed>();
+        app.add_event::<Respawned>();
+        app.add_event::<ScriptedMove>();
         app.add_systems(Startup, setup_player);
 
         app.add_systems(FixedUpdate, (
+            capture_input_frame.before(movement_system).run_if(input_capture_enabled),
             movement_system,
-            apply_player_motion.after(movement_system),
-            update_grounded_flag_and_snap.after(apply_player_motion),
+            read_character_controller.after(drive_character_controller).run_if(rapier_controller),
+            drive_character_controller.after(movement_system).run_if(rapier_controller),
+            apply_player_motion.after(movement_system).after(move_platforms).run_if(custom_controller),
+            face_movement_direction.after(movement_system),
+            kick_camera_on_dash.after(movement_system),
+            stop_at_ceiling.after(apply_player_motion).run_if(custom_controller),
+            update_grounded_flag_and_snap.after(stop_at_ceiling).run_if(custom_controller),
+            apply_ground_effects.after(update_grounded_flag_and_snap).run_if(custom_controller),
+            check_crouch_headroom.after(update_grounded_flag_and_snap),
+            assist_away_from_edge.after(update_grounded_flag_and_snap).run_if(custom_controller),
+            track_airtime.after(update_grounded_flag_and_snap).after(read_character_controller),
+            recover_stuck_airborne.after(track_airtime).run_if(custom_controller),
+            respawn_below_kill_plane.after(recover_stuck_airborne),
+        ).run_if(dialog_closed).run_if(level_ready));
+
+        app.add_systems(Update, (
+            toggle_auto_run,
+            latch_jump_input,
+            latch_dash_input,
+            release_input_on_gamepad_disconnect,
+            toggle_cursor_capture,
+            orbit_camera_with_mouse.after(toggle_cursor_capture).before(sync_camera_yaw),
+            zoom_camera_with_wheel,
+            toggle_camera_mode,
+            shake_camera,
+            sync_camera_yaw,
+            stabilize_camera_roll.after(shake_camera),
+            settle_camera_kick.after(shake_camera),
+            follow_player_camera
+                .after(stabilize_camera_roll)
+                .after(settle_camera_kick)
+                .after(zoom_camera_with_wheel)
+                .after(toggle_camera_mode),
+            fade_occluded_player.after(follow_player_camera),
+            aim_reticle.after(follow_player_camera),
+            draw_reticle_marker.after(aim_reticle),
         ));
 
-        app.add_systems(Update, follow_player_camera);
+        #[cfg(target_arch = "wasm32")]
+        app.add_systems(
+            PreUpdate,
+            crate::systems::release_input_on_focus_lost.after(bevy::input::InputSystem)
+        );
     }
 }
 
 #[derive(Component)]
 pub struct Player;
 
+#[derive(Resource)]
+pub struct PlayerDimensions {
+    pub half_height: f32,
+    pub crouch_half_height: f32,
+
+    // grows (> 0, sticky edges) or shrinks (< 0, fall earlier) the footprint
+    pub ledge_tolerance: f32,
+}
+
+impl Default for PlayerDimensions {
+    fn default() -> Self {
+        Self {
+            half_height: PLAYER_HALF_HEIGHT,
+            crouch_half_height: PLAYER_CROUCH_HALF_HEIGHT,
+            ledge_tolerance: 0.0,
+        }
+    }
+}
+
+impl PlayerDimensions {
+    pub fn current_half_height(&self, crouching: bool) -> f32 {
+        if crouching { self.crouch_half_height.min(self.half_height) } else { self.half_height }
+    }
+
+    pub fn foot_half_extents(&self) -> Vec3 {
+        Vec3::new(
+            (FOOT_HALF_X + self.ledge_tolerance).max(0.01),
+            FOOT_HALF_Y,
+            (FOOT_HALF_Z + self.ledge_tolerance).max(0.01),
+        )
+    }
+}
+
+/// Sent on landing: how long we were airborne, how high above the launch point we got
+/// and how fast we were falling at touchdown.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct AirtimeStats {
+    pub airtime: f32,
+    pub peak_height: f32,
+    pub impact_speed: f32,
+}
+
+/// Sent on the tick the player touches down (once per landing, either controller).
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Landed {
+    // downward speed at touchdown (units/s, >= 0)
+    pub impact_speed: f32,
+    // ground contact under the player at touchdown
+    pub position: Vec3,
+}
+
 #[derive(Component)]
-pub struct FollowPlayerCamera;
+pub struct FollowPlayerCamera {
+    // heading the camera looks along, clockwise from above (0 = looking at -Z, i.e. the
+    // camera sits on +Z; PI/2 = looking at +X)
+    pub yaw: f32,
+    // elevation above the player (radians), clamped by CameraFollowSettings
+    pub pitch: f32,
+
+    // eased follow height (None until the first follow frame)
+    pub smoothed_y: Option<f32>,
+
+    // horizontal point the camera frames; only moves once the player leaves the deadzone
+    pub focus_xz: Option<Vec2>,
+
+    // eased camera position and look target (None until the first follow frame)
+    pub smoothed_translation: Option<Vec3>,
+    pub smoothed_look: Option<Vec3>,
+}
+
+impl Default for FollowPlayerCamera {
+    fn default() -> Self {
+        Self {
+            yaw: 0.0,
+            pitch: CAMERA_PITCH,
+            smoothed_y: None,
+            focus_xz: None,
+            smoothed_translation: None,
+            smoothed_look: None,
+        }
+    }
+}
+
+impl FollowPlayerCamera {
+    /// Drops the eased state so the next follow frame snaps into place.
+    pub fn reset_smoothing(&mut self) {
+        self.smoothed_y = None;
+        self.focus_xz = None;
+        self.smoothed_translation = None;
+        self.smoothed_look = None;
+    }
+}
+
+#[derive(Resource)]
+pub struct CameraFollowSettings {
+    // exponential rate (1/s) the camera height eases toward the player's Y;
+    // horizontal follow stays tight so steps don't jerk the view
+    pub camera_vertical_lerp: f32,
+
+    // half extents (camera right, camera forward) the player may drift from
+    // the focus before the camera follows; zero = always follow
+    pub deadzone: Vec2,
+
+    // with a FrameTarget: camera distance per unit of player-target separation,
+    // never closer than the zoom distance nor further than frame_max_distance
+    pub frame_distance_per_spread: f32,
+    pub frame_max_distance: f32,
+
+    // mouse orbit (cursor captured): radians per pixel, pitch limits in radians
+    pub mouse_sensitivity: f32,
+    pub min_pitch: f32,
+    pub max_pitch: f32,
+
+    // geometry between the player and the camera pulls the camera in,
+    // but never closer than this
+    pub min_camera_distance: f32,
+
+    // exponential rates (1/s) the camera position and look target ease toward
+    // their goals; 0 = snap every frame
+    pub position_smoothing: f32,
+    pub look_smoothing: f32,
+}
+
+impl Default for CameraFollowSettings {
+    fn default() -> Self {
+        Self {
+            camera_vertical_lerp: 8.0,
+            deadzone: Vec2::ZERO,
+            frame_distance_per_spread: 1.2,
+            frame_max_distance: 40.0,
+            mouse_sensitivity: 0.003,
+            min_pitch: -0.2,
+            max_pitch: 1.4,
+            min_camera_distance: 1.5,
+            position_smoothing: 12.0,
+            look_smoothing: 20.0,
+        }
+    }
+}
+
+/// On the follow camera: frame this entity together with the player (boss,
+/// waypoint). The camera aims at their midpoint and pulls back as they separate.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct FrameTarget(pub Entity);
+
+/// Adds `trauma` (0..1) to the camera shake (landings, hits, ...).
+#[derive(Event, Clone, Copy, Debug)]
+pub struct CameraShake {
+    pub trauma: f32,
+}
+
+/// Camera shake state. Shake strength is trauma^2, so small bumps stay subtle;
+/// trauma decays by `decay_per_s`. The shake drives the CameraKick (position) and
+/// CameraRoll springs; amplitudes are the offset and roll they follow at full trauma
+/// (units / radians), `frequency` is how fast the wobble moves (Hz-ish).
+#[derive(Resource)]
+pub struct CameraTrauma {
+    pub trauma: f32,
+    pub decay_per_s: f32,
+    pub position_amplitude: f32,
+    pub rotation_amplitude: f32,
+    pub frequency: f32,
+
+    // landings faster than landing_min_speed shake, reaching landing_trauma at landing_full_speed
+    pub landing_trauma: f32,
+    pub landing_min_speed: f32,
+    pub landing_full_speed: f32,
+}
+
+impl Default for CameraTrauma {
+    fn default() -> Self {
+        Self {
+            trauma: 0.0,
+            decay_per_s: 1.5,
+            position_amplitude: 0.3,
+            rotation_amplitude: 0.05,
+            frequency: 15.0,
+
+            landing_trauma: 0.6,
+            landing_min_speed: 12.0,
+            landing_full_speed: 18.0, // terminal fall speed with default tuning
+        }
+    }
+}
+
+impl CameraTrauma {
+    /// Trauma a landing at `impact_speed` (units/s, downward) adds; 0 = no shake.
+    pub fn landing(&self, impact_speed: f32) -> f32 {
+        let span = (self.landing_full_speed - self.landing_min_speed).max(0.001);
+        let t = ((impact_speed - self.landing_min_speed) / span).clamp(0.0, 1.0);
+        t * self.landing_trauma
+    }
+
+    /// Positional offset and roll wobble at `elapsed` seconds.
+    fn shake(&self, elapsed: f32) -> (Vec3, f32) {
+        let strength = self.trauma * self.trauma;
+        if strength <= 0.0 {
+            return (Vec3::ZERO, 0.0);
+        }
+
+        // smooth pseudo-noise: two incommensurate sines per channel, each channel phase-shifted
+        let t = elapsed * self.frequency;
+        let noise = |seed: f32| ((t + seed).sin() + 0.5 * (2.3 * t + 1.7 * seed).sin()) / 1.5;
+
+        let position = Vec3::new(noise(0.0), noise(11.0), noise(23.0)) * self.position_amplitude;
+        let roll = noise(37.0) * self.rotation_amplitude;
+        (position * strength, roll * strength)
+    }
+}
+
+/// Third-person follow (default) or first-person at the player's eyes, toggled with
+/// CAMERA_MODE_KEY. Both aim by the camera's yaw/pitch, so movement stays camera-relative.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CameraMode {
+    #[default]
+    ThirdPerson,
+    FirstPerson,
+}
+
+/// Where the first-person reticle (screen center) points: the first surface along the
+/// camera's view, with its normal. None in third person or when nothing is in range.
+#[derive(Resource, Default)]
+pub struct ReticleTarget {
+    pub hit: Option<(Vec3, Vec3)>,
+}
+
+/// Scroll-wheel follow distance. The wheel moves `target` by `step` per notch within
+/// min..max; `current` (what the camera uses) eases toward it at `smoothing` (1/s).
+/// `min` is kept at or above CameraFollowSettings::min_camera_distance.
+#[derive(Resource)]
+pub struct CameraZoom {
+    pub current: f32,
+    pub target: f32,
+    pub min: f32,
+    pub max: f32,
+    pub step: f32,
+    pub smoothing: f32,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self {
+            current: CAMERA_DISTANCE,
+            target: CAMERA_DISTANCE,
+            min: 3.0,
+            max: 25.0,
+            step: 1.0,
+            smoothing: 12.0,
+        }
+    }
+}
+
+/// Point the camera should aim at and how far it should sit to keep both `player`
+/// and `target` in view, starting from the normal follow `distance`.
+pub fn frame_player_and_target(
+    settings: &CameraFollowSettings,
+    distance: f32,
+    player: Vec3,
+    target: Vec3
+) -> (Vec3, f32) {
+    let spread = player.distance(target);
+    let max = settings.frame_max_distance.max(distance);
+    let framed = (spread * settings.frame_distance_per_spread).clamp(distance, max);
+    ((player + target) / 2.0, framed)
+}
+
+/// Transient camera roll (radians) pulled back to level by a damped spring.
+/// Camera shake (and anything else, e.g. banking) pushes it with `impulse`.
+#[derive(Resource)]
+pub struct CameraRoll {
+    pub angle: f32,
+    pub velocity: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+}
+
+impl Default for CameraRoll {
+    fn default() -> Self {
+        Self {
+            angle: 0.0,
+            velocity: 0.0,
+            stiffness: 120.0,
+            damping: 8.0,
+        }
+    }
+}
+
+impl CameraRoll {
+    pub fn impulse(&mut self, angular_velocity: f32) {
+        self.velocity += angular_velocity;
+    }
+}
+
+/// Transient camera position offset pulled back to zero by a damped spring.
+/// A dash kicks it opposite the dash direction at `dash_intensity` (units/s, 0 = off);
+/// camera shake pushes it around too.
+#[derive(Resource)]
+pub struct CameraKick {
+    pub offset: Vec3,
+    pub velocity: Vec3,
+    pub stiffness: f32,
+    pub damping: f32,
+    pub dash_intensity: f32,
+}
+
+impl Default for CameraKick {
+    fn default() -> Self {
+        Self {
+            offset: Vec3::ZERO,
+            velocity: Vec3::ZERO,
+            stiffness: 150.0,
+            damping: 14.0,
+            dash_intensity: 6.0,
+        }
+    }
+}
+
+impl CameraKick {
+    pub fn impulse(&mut self, velocity: Vec3) {
+        self.velocity += velocity;
+    }
+}
+
+#[derive(Resource)]
+pub struct PlayerSpawn {
+    pub position: Vec3,
+    pub facing: Vec2,
+}
+
+impl Default for PlayerSpawn {
+    fn default() -> Self {
+        Self {
+            position: Vec3::new(0.0, 2.0, 0.0),
+            facing: Vec2::Y,
+        }
+    }
+}
+
+/// Falling below `y` respawns the player at PlayerSpawn.
+#[derive(Resource)]
+pub struct KillPlane {
+    pub y: f32,
+}
+
+impl Default for KillPlane {
+    fn default() -> Self {
+        Self { y: -50.0 }
+    }
+}
+
+/// Sent after the player was put back at PlayerSpawn.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct Respawned {
+    pub position: Vec3,
+}
+
+/// Fades the player model when geometry sits between it and the camera.
+#[derive(Resource)]
+pub struct OcclusionFade {
+    pub enabled: bool,
+    pub occluded_alpha: f32,
+    // alpha change per second
+    pub fade_speed: f32,
+}
+
+impl Default for OcclusionFade {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            occluded_alpha: 0.35,
+            fade_speed: 4.0,
+        }
+    }
+}
+
+/// While a menu/dialog is open the FixedUpdate movement/physics chain is frozen;
+/// the camera keeps updating.
+#[derive(Resource, Default)]
+pub struct DialogState {
+    pub open: bool,
+}
+
+pub fn dialog_closed(dialog: Res<DialogState>) -> bool {
+    !dialog.open
+}
+
+/// Which code moves and grounds the player: the hand-rolled sweeps/snapping in this
+/// file, or rapier's KinematicCharacterController (autostep, slopes, snapping done
+/// by rapier; no ground effects or ground_normal). Read once by setup_player.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PlayerController {
+    #[default]
+    Custom,
+    Rapier,
+}
+
+fn custom_controller(controller: Res<PlayerController>) -> bool {
+    *controller == PlayerController::Custom
+}
+
+fn rapier_controller(controller: Res<PlayerController>) -> bool {
+    *controller == PlayerController::Rapier
+}
+
+/// Which entity carries the SpatialListener: the camera (3D sounds pan with the
+/// view) or the player (with the character). Read once by setup_player.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ListenerMode {
+    #[default]
+    Camera,
+    Player,
+}
+
+/// Collision groups the player's ground checks test against; grounds outside
+/// the mask are ignored (e.g. a ghost state falling through certain platforms).
+#[derive(Resource, Default)]
+pub struct PlayerGroundMask(pub CollisionGroups);
+
+pub type GroundEffectFn = Box<dyn Fn(&EntityRef, &mut MovementState) + Send + Sync>;
 
+/// Callbacks run every tick the player stands on a Ground tagged with a given
+/// marker component (jump pads, ice, triggers, ...). The callback also gets the
+/// ground entity so it can read per-surface data.
+#[derive(Resource, Default)]
+pub struct GroundEffects {
+    effects: Vec<(TypeId, GroundEffectFn)>,
+}
+
+impl GroundEffects {
+    pub fn register<C: Component>(
+        &mut self,
+        effect: impl Fn(&EntityRef, &mut MovementState) + Send + Sync + 'static
+    ) {
+        self.effects.push((TypeId::of::<C>(), Box::new(effect)));
+    }
+
+    pub fn apply(&self, ground: &EntityRef, st: &mut MovementState) {
+        for (marker, effect) in &self.effects {
+            if ground.contains_type_id(*marker) {
+                effect(ground, st);
+            }
+        }
+    }
+}
+
+/// Runs the GroundEffects callbacks for the Ground the player is standing on. Exclusive
+/// because the callbacks get an EntityRef, and read-everything entity access can't
+/// share a system with ResMut<MovementState>.
+pub fn apply_ground_effects(world: &mut World) {
+    let Some(standing_on) = world.resource::<MovementState>().standing_on else { return; };
+
+    world.resource_scope(|world, effects: Mut<GroundEffects>| {
+        world.resource_scope(|world, mut st: Mut<MovementState>| {
+            let Some(ground) = world.get_entity(standing_on) else { return; };
+            if ground.contains::<Ground>() && !ground.contains::<Player>() {
+                effects.apply(&ground, &mut st);
+            }
+        });
+    });
+}
+
+fn boost_pad_effect(ground: &EntityRef, st: &mut MovementState) {
+    if let Some(pad) = ground.get::<BoostPad>() {
+        st.apply_boost(pad.direction, pad.speed);
+    }
+}
+
+/// Yaw that puts the camera behind `facing` (movement-space xz, y maps to z).
+pub fn camera_yaw_behind(facing: Vec2) -> f32 {
+    let f = facing.normalize_or(Vec2::Y);
+    f32::atan2(f.x, -f.y)
+}
+
+/// Y rotation that points a model's -Z along `dir` (movement-space xz).
+pub fn facing_rotation(dir: Vec2) -> Quat {
+    let d = dir.normalize_or(Vec2::Y);
+    Quat::from_rotation_y(f32::atan2(-d.x, -d.y))
+}
+
+// Camera position relative to the player: behind the heading `yaw` and raised by `pitch`.
+fn camera_offset(yaw: f32, pitch: f32, distance: f32) -> Vec3 {
+    let back = Vec3::new(0.0, pitch.sin(), pitch.cos()) * distance;
+    Quat::from_rotation_y(-yaw) * back
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn setup_player(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    skybox: Res<SkyboxSettings>,
+    spawn: Res<PlayerSpawn>,
+    controller: Res<PlayerController>,
+    listener: Res<ListenerMode>,
+    mut st: ResMut<MovementState>,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>
 ) {
+    st.dir = spawn.facing.normalize_or(Vec2::Y);
+    let yaw = camera_yaw_behind(st.dir);
+    st.camera_yaw = yaw;
+
     // Start above where ground likely is; ground snap will correct on first tick.
-    commands.spawn((
+    let mut player = commands.spawn((
         PbrBundle {
             mesh: meshes.add(Mesh::from(Cuboid::new(1.0, 1.0, 1.0))),
             material: materials.add(Color::srgb(0.8, 0.8, 0.9)),
-            transform: Transform::from_xyz(0.0, 2.0, 0.0),
+            transform: Transform::from_translation(spawn.position).with_rotation(
+                facing_rotation(st.dir)
+            ),
             ..default()
         },
         Player,
+        Health::default(),
         RigidBody::KinematicPositionBased,
+        // ground/ceiling/occlusion queries all exclude this via exclude_collider
+        Collider::capsule_y(PLAYER_HALF_HEIGHT - PLAYER_RADIUS, PLAYER_RADIUS),
     ));
 
-    commands.spawn((
+    if *controller == PlayerController::Rapier {
+        player.insert(KinematicCharacterController {
+            autostep: Some(CharacterAutostep {
+                max_height: CharacterLength::Absolute(0.3),
+                min_width: CharacterLength::Absolute(0.2),
+                include_dynamic_bodies: false,
+            }),
+            snap_to_ground: Some(CharacterLength::Absolute(0.2)),
+            max_slope_climb_angle: st.max_walkable_slope_angle.to_radians(),
+            min_slope_slide_angle: st.max_walkable_slope_angle.to_radians(),
+            ..default()
+        });
+    }
+
+    if *listener == ListenerMode::Player {
+        player.insert(SpatialListener::new(LISTENER_EAR_GAP));
+    }
+
+    let mut camera = commands.spawn((
         Camera3dBundle {
-            transform: Transform::from_xyz(0.0, CAMERA_HEIGHT, CAMERA_DISTANCE).looking_at(
-                Vec3::ZERO,
+            transform: Transform::from_translation(spawn.position + camera_offset(yaw, CAMERA_PITCH, CAMERA_DISTANCE)).looking_at(
+                spawn.position,
                 Vec3::Y
             ),
             ..default()
         },
-        FollowPlayerCamera,
-        SkyboxHandle(asset_server.load("skybox/skybox.ktx2")),
+        FollowPlayerCamera { yaw, ..default() },
     ));
+
+    // with the skybox off the KTX2 is never loaded; the clear color shows instead
+    if skybox.enabled {
+        camera.insert(SkyboxHandle(asset_server.load("skybox/skybox.ktx2")));
+    }
+
+    if *listener == ListenerMode::Camera {
+        camera.insert(SpatialListener::new(LISTENER_EAR_GAP));
+    }
+}
+
+/// Crouch: squash the body along Y and move the center so the feet stay put.
+/// Returns the current half height.
+fn apply_crouch_scale(dims: &PlayerDimensions, crouching: bool, t: &mut Transform) -> f32 {
+    let full = dims.half_height.max(0.001);
+    let half = dims.current_half_height(crouching);
+    let prev_half = full * t.scale.y;
+    if half != prev_half {
+        t.translation.y += half - prev_half;
+        t.scale.y = half / full;
+    }
+    half
+}
+
+/// PlayerController::Rapier: hand this tick's motion to the character controller.
+/// Rapier applies it once per frame, so several ticks in one frame add up.
+pub fn drive_character_controller(
+    time: Res<Time>,
+    dims: Res<PlayerDimensions>,
+    st: Res<MovementState>,
+    mut q: Query<(&mut KinematicCharacterController, &mut Transform), With<Player>>
+) {
+    let Ok((mut controller, mut t)) = q.get_single_mut() else {
+        return;
+    };
+
+    apply_crouch_scale(&dims, st.is_crouching, &mut t);
+
+    let fall_y = if st.is_falling { st.fall_vel_y } else { 0.0 };
+    let step = Vec3::new(st.velocity.x, fall_y, st.velocity.y) * time.delta_seconds();
+    controller.translation = Some(controller.translation.unwrap_or(Vec3::ZERO) + step);
+}
+
+/// PlayerController::Rapier: take grounded from the controller's last move. Runs after
+/// movement_system like the custom grounding, so a touchdown still sees the impact speed.
+pub fn read_character_controller(
+    dims: Res<PlayerDimensions>,
+    mut st: ResMut<MovementState>,
+    q: Query<(&KinematicCharacterControllerOutput, &Transform), With<Player>>
+) {
+    let Ok((output, t)) = q.get_single() else {
+        return;
+    };
+
+    // still rising from a jump: the pre-jump output says grounded
+    let grounded = output.grounded && st.fall_vel_y <= 0.0;
+    st.is_falling = !grounded;
+    if grounded {
+        // the controller rests the capsule on the ground, so the contact is at the feet
+        st.ground_point = t.translation - Vec3::Y * dims.current_half_height(st.is_crouching);
+        st.grounded_ticks = st.grounded_ticks.saturating_add(1);
+        st.airborne_ticks = 0;
+    } else {
+        st.airborne_ticks = st.airborne_ticks.saturating_add(1);
+        st.grounded_ticks = 0;
+    }
+}
+
+/// Sweeps `shape` from `pos` along horizontal `delta` against the filter's walls:
+/// stops just short of the first hit and slides the rest along its surface.
+/// Returns the delta that can actually be applied.
+fn slide_along_walls(
+    rapier: &RapierContext,
+    shape: &Collider,
+    pos: Vec3,
+    delta: Vec2,
+    filter: QueryFilter
+) -> Vec2 {
+    let mut start = pos;
+    let mut remaining = Vec3::new(delta.x, 0.0, delta.y);
+    let mut moved = Vec3::ZERO;
+
+    for _ in 0..WALL_SLIDE_ITERATIONS {
+        let len = remaining.length();
+        if len <= 1e-6 {
+            break;
+        }
+
+        let options = ShapeCastOptions {
+            max_time_of_impact: len + WALL_SKIN,
+            compute_impact_geometry_on_penetration: true,
+            ..default()
+        };
+        let Some((_, hit)) = rapier.cast_shape(start, Quat::IDENTITY, remaining / len, shape, options, filter) else {
+            moved += remaining;
+            break;
+        };
+
+        // advance up to the wall, then keep only the part of the move along it
+        let advance = (hit.time_of_impact - WALL_SKIN).clamp(0.0, len);
+        let step = remaining / len * advance;
+        moved += step;
+        start += step;
+
+        let normal = hit.details.map_or(-remaining / len, |d| d.normal1);
+        let normal = Vec3::new(normal.x, 0.0, normal.z).normalize_or_zero();
+        remaining -= step;
+        remaining -= normal * remaining.dot(normal).min(0.0);
+    }
+
+    moved.xz()
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn apply_player_motion(
     time: Res<Time>,
+    rapier: Res<RapierContext>,
+    dims: Res<PlayerDimensions>,
+    mask: Res<PlayerGroundMask>,
     st: Res<MovementState>,
-    mut q: Query<&mut Transform, With<Player>>
+    ground_q: Query<(), With<Ground>>,
+    wall_q: Query<(), With<Wall>>,
+    platforms: Query<&MovingPlatform>,
+    mut q: Query<(Entity, &mut Transform), With<Player>>
 ) {
     let dt = time.delta_seconds();
-    let Ok(mut t) = q.get_single_mut() else {
+    let Ok((player_e, mut t)) = q.get_single_mut() else {
         return;
     };
 
+    let half = apply_crouch_scale(&dims, st.is_crouching, &mut t);
+
+    // ride whatever platform we stand on (grounding snaps us onto its new top)
+    if let Some(platform) = st.standing_on.and_then(|e| platforms.get(e).ok()) {
+        t.translation += platform.delta;
+    }
+
     // Horizontal ALWAYS (movement_system decays to 0 while falling)
-    t.translation.x += st.velocity.x * dt;
-    t.translation.z += st.velocity.y * dt;
+    // Vertical: fall velocity while falling, otherwise follow the ground slope
+    let steps = st.movement_substeps.max(1);
+    let vel_y = if st.is_falling { st.fall_vel_y } else { slope_rise(st.ground_normal, st.velocity) };
+    let step = Vec3::new(st.velocity.x, vel_y, st.velocity.y) * (dt / steps as f32);
+
+    // horizontal moves are swept against walls with the body's capsule
+    let body = Collider::capsule_y((half - PLAYER_RADIUS).max(0.0), PLAYER_RADIUS);
+    let is_wall = |e| wall_q.contains(e);
+    let wall_filter = QueryFilter::default()
+        .exclude_collider(player_e)
+        .predicate(&is_wall);
+
+    if steps == 1 {
+        let horizontal = slide_along_walls(&rapier, &body, t.translation, step.xz(), wall_filter);
+        t.translation += Vec3::new(horizontal.x, step.y, horizontal.y);
+        return;
+    }
+
+    // substeps: probe the footprint after each slice of a descent and stop falling
+    // once it touches ground; grounding then snaps onto that surface
+    let foot_half = dims.foot_half_extents();
+    let foot_shape = Collider::cuboid(foot_half.x, foot_half.y, foot_half.z);
+    let is_ground = |e| ground_q.contains(e);
+    let filter = QueryFilter::default()
+        .exclude_collider(player_e)
+        .groups(mask.0)
+        .predicate(&is_ground);
+
+    let mut landed = false;
+    for _ in 0..steps {
+        let horizontal = slide_along_walls(&rapier, &body, t.translation, step.xz(), wall_filter);
+        t.translation.x += horizontal.x;
+        t.translation.z += horizontal.y;
+        if landed {
+            continue;
+        }
+
+        t.translation.y += step.y;
+        if !st.is_falling || step.y >= 0.0 {
+            continue;
+        }
+
+        let foot_center = Vec3::new(
+            t.translation.x,
+            (t.translation.y - half) + foot_half.y - FOOT_BELOW_FEET,
+            t.translation.z,
+        );
+        landed = rapier.intersection_with_shape(foot_center, Quat::IDENTITY, &foot_shape, filter).is_some();
+    }
+}
 
-    // Vertical ONLY depends on falling flag and fall velocity
-    if st.is_falling {
-        t.translation.y += st.fall_vel_y * dt;
+/// Vertical speed that keeps horizontal `velocity` (movement-space xz) on the plane
+/// with `normal`, so walking follows ramps instead of clipping into or off them.
+pub fn slope_rise(normal: Vec3, velocity: Vec2) -> f32 {
+    if normal.y <= 0.01 {
+        return 0.0;
+    }
+    -(normal.x * velocity.x + normal.z * velocity.y) / normal.y
+}
+
+/// Turns the player toward `dir` while moving; when stopped it keeps its last facing.
+/// Only touches rotation, so it doesn't interfere with motion/grounding.
+pub fn face_movement_direction(
+    time: Res<Time>,
+    st: Res<MovementState>,
+    mut q: Query<&mut Transform, With<Player>>
+) {
+    if st.speed <= st.stop_epsilon {
+        return;
     }
+
+    let Ok(mut t) = q.get_single_mut() else {
+        return;
+    };
+
+    let target = facing_rotation(st.dir);
+    let k = 1.0 - (-st.turn_rate * time.delta_seconds()).exp();
+    t.rotation = t.rotation.slerp(target, k);
 }
 
-/// 1) Detect grounded by footprint intersection vs Ground.
-/// 2) If grounded: snap player y to Ground top surface + PLAYER_HALF_HEIGHT.
-///    This removes the need for any constant GROUND_Y.
+/// 1) Detect grounded by casting the footprint down from the player center onto Ground.
+/// 2) If grounded: snap player y to the contact height + half height and record the
+///    ground normal (slopes, rotated boxes and meshes all work the same way).
 pub fn update_grounded_flag_and_snap(
     rapier: Res<RapierContext>,
+    dims: Res<PlayerDimensions>,
+    mask: Res<PlayerGroundMask>,
     mut st: ResMut<MovementState>,
-    // We need actual data for ground entities:
-    ground_q: Query<(&GlobalTransform, &Collider), With<Ground>>,
-    mut player_q: Query<(Entity, &GlobalTransform, &mut Transform), With<Player>>,
+    ground_q: Query<(), With<Ground>>,
+    mut player_q: Query<(Entity, &mut Transform), With<Player>>,
 ) {
-    let Ok((player_e, gt, mut t)) = player_q.get_single_mut() else { return; };
-    let pos = gt.translation();
+    // Transform, not GlobalTransform: apply_player_motion just moved us this tick
+    // and the player has no parent
+    let Ok((player_e, mut t)) = player_q.get_single_mut() else { return; };
+    let pos = t.translation;
+    let half_height = dims.current_half_height(st.is_crouching);
 
-    // Footprint box center at player feet
-    let foot_center = Vec3::new(
-        pos.x,
-        (pos.y - PLAYER_HALF_HEIGHT) + FOOT_HALF_Y - FOOT_BELOW_FEET,
-        pos.z,
-    );
+    let foot_half = dims.foot_half_extents();
+    let foot_shape = Collider::cuboid(foot_half.x, foot_half.y, foot_half.z);
 
-    let foot_shape = Collider::cuboid(FOOT_HALF_X, FOOT_HALF_Y, FOOT_HALF_Z);
+    let is_ground = |e| ground_q.contains(e);
+    let filter = QueryFilter::default()
+        .exclude_collider(player_e)
+        .groups(mask.0)
+        .predicate(&is_ground);
 
-    let filter = QueryFilter::default().exclude_collider(player_e);
+    // Sweep the thin footprint from the body center down to just below the feet:
+    // starting at the center also catches us after sinking into the ground.
+    let probe = half_height - foot_half.y + FOOT_BELOW_FEET;
+    let options = ShapeCastOptions {
+        max_time_of_impact: probe.max(0.0),
+        compute_impact_geometry_on_penetration: true,
+        ..default()
+    };
+    let hit = rapier.cast_shape(pos, Quat::IDENTITY, Vec3::NEG_Y, &foot_shape, options, filter);
 
-    // Find all intersections, but only count Ground entities.
-    let mut grounded = false;
-    let mut best_top_y: Option<f32> = None;
-
-    rapier.intersections_with_shape(
-        foot_center,
-        Quat::IDENTITY,
-        &foot_shape,
-        filter,
-        |hit_entity| {
-            let Ok((g_gt, g_col)) = ground_q.get(hit_entity) else {
-                // not Ground => ignore
-                return true; // keep searching
-            };
-
-            grounded = true;
-
-            // Compute top surface Y for cuboid colliders (perfect for your box maps).
-            // NOTE: This assumes the ground cuboids are not rotated.
-            if let Some(cub) = g_col.as_cuboid() {
-                let half_y = cub.half_extents().y;
-                let top_y = g_gt.translation().y + half_y;
-
-                best_top_y = Some(match best_top_y {
-                    Some(cur) => cur.max(top_y),
-                    None => top_y,
-                });
-            }
+    // normal1 is the ground collider's outward normal, in world space
+    let normal = hit.map(|(_, h)| h.details.map_or(Vec3::Y, |d| d.normal1.normalize_or(Vec3::Y)));
+    let too_steep = normal.is_some_and(|n| {
+        n.y.clamp(-1.0, 1.0).acos() > st.max_walkable_slope_angle.to_radians()
+    });
 
-            true // keep searching (we want highest top_y under the footprint)
-        },
-    );
+    let mut grounded = hit.is_some() && !too_steep;
 
-    st.is_falling = !grounded;
+    // Still rising from a jump: don't let the footprint snap us back down.
+    if st.fall_vel_y > 0.0 {
+        grounded = false;
+    }
 
-    // If grounded, snap to the best ground height.
-    // This removes jitter and eliminates any need for a GROUND_Y constant.
+    st.is_falling = !grounded;
     if grounded {
-        if let Some(top_y) = best_top_y {
-            t.translation.y = top_y + PLAYER_HALF_HEIGHT;
+        st.grounded_ticks = st.grounded_ticks.saturating_add(1);
+        st.airborne_ticks = 0;
+    } else {
+        st.airborne_ticks = st.airborne_ticks.saturating_add(1);
+        st.grounded_ticks = 0;
+    }
+
+    // Too steep: stay airborne but push out along the normal, which moves us
+    // downhill so gravity slides the player off instead of sinking in.
+    if let (true, Some((_, hit)), Some(n)) = (too_steep, hit, normal) {
+        let rest_y = pos.y - foot_half.y - hit.time_of_impact + half_height;
+        let depth = rest_y - t.translation.y;
+        if depth > 0.0 {
+            t.translation += n * (depth * n.y);
         }
     }
-}
 
-pub fn follow_player_camera(
-    player_q: Query<&Transform, With<Player>>,
-    mut cam_q: Query<&mut Transform, (With<FollowPlayerCamera>, Without<Player>)>
-) {
-    let Ok(player_t) = player_q.get_single() else {
+    let Some((standing_on, hit)) = hit.filter(|_| grounded) else {
+        st.ground_normal = Vec3::Y;
+        st.standing_on = None;
         return;
     };
-    let Ok(mut cam_t) = cam_q.get_single_mut() else {
+
+    st.standing_on = Some(standing_on);
+
+    st.ground_normal = normal.unwrap_or(Vec3::Y);
+
+    // Snap the feet to the contact height.
+    // This removes jitter and eliminates any need for a GROUND_Y constant.
+    // With snapping off, only correct penetration and rest where gravity left us.
+    let contact_y = pos.y - foot_half.y - hit.time_of_impact;
+    st.ground_point = Vec3::new(pos.x, contact_y, pos.z);
+    let rest_y = contact_y + half_height;
+    t.translation.y = if st.ground_snap_enabled { rest_y } else { t.translation.y.max(rest_y) };
+}
+
+/// Edge assist: split the footprint into quadrants; if some have no ground under
+/// them and we're not moving toward that side, nudge back onto the platform.
+pub fn assist_away_from_edge(
+    time: Res<Time>,
+    rapier: Res<RapierContext>,
+    dims: Res<PlayerDimensions>,
+    mask: Res<PlayerGroundMask>,
+    st: Res<MovementState>,
+    ground_q: Query<(), With<Ground>>,
+    mut player_q: Query<(Entity, &mut Transform), With<Player>>,
+) {
+    if !st.edge_assist || st.is_falling {
         return;
-    };
+    }
+
+    let Ok((player_e, mut t)) = player_q.get_single_mut() else { return; };
+    let pos = t.translation;
 
-    let player_pos = player_t.translation;
-    let offset = Vec3::new(0.0, CAMERA_HEIGHT, CAMERA_DISTANCE);
+    let foot_half = dims.foot_half_extents();
+    let quad_shape = Collider::cuboid(foot_half.x / 2.0, foot_half.y, foot_half.z / 2.0);
+    let half_height = dims.current_half_height(st.is_crouching);
+    let foot_y = (pos.y - half_height) + foot_half.y - FOOT_BELOW_FEET;
 
-    cam_t.translation = player_pos + offset;
-    cam_t.look_at(player_pos, Vec3::Y);
+    let is_ground = |e| ground_q.contains(e);
+    let filter = QueryFilter::default()
+        .exclude_collider(player_e)
+        .groups(mask.0)
+        .predicate(&is_ground);
+
+    // sum of the directions of unsupported quadrants points at the gap
+    let mut gap = Vec2::ZERO;
+    for (sx, sz) in [(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)] {
+        let offset = Vec2::new(sx * foot_half.x / 2.0, sz * foot_half.z / 2.0);
+        let center = Vec3::new(pos.x + offset.x, foot_y, pos.z + offset.y);
+
+        if rapier.intersection_with_shape(center, Quat::IDENTITY, &quad_shape, filter).is_none() {
+            gap += offset;
+        }
+    }
+
+    let Some(gap_dir) = gap.try_normalize() else { return; };
+
+    // moving toward the gap is intentional
+    if st.velocity.dot(gap_dir) > 0.0 {
+        return;
+    }
+
+    let nudge = -gap_dir * st.edge_assist_speed * time.delta_seconds();
+    t.translation.x += nudge.x;
+    t.translation.z += nudge.y;
+}
+
+/// While moving up, sweep a ball as wide as the capsule's top cap over this tick's rise;
+/// if the head ran into an underside, cancel the upward velocity and keep the head just
+/// under it. Walls and step sides next to the body aren't above the head, so they don't count.
+pub fn stop_at_ceiling(
+    time: Res<Time>,
+    rapier: Res<RapierContext>,
+    dims: Res<PlayerDimensions>,
+    mut st: ResMut<MovementState>,
+    mut player_q: Query<(Entity, &mut Transform), With<Player>>,
+) {
+    if !st.is_falling || st.fall_vel_y <= 0.0 {
+        return;
+    }
+
+    let Ok((player_e, mut t)) = player_q.get_single_mut() else { return; };
+
+    // apply_player_motion already moved us up; sweep from where the head started the tick
+    let half_height = dims.current_half_height(st.is_crouching);
+    let rise = st.fall_vel_y * time.delta_seconds();
+    let head_y = t.translation.y + half_height - rise;
+
+    // a hair narrower than the capsule so walls it slides along aren't touched
+    let radius = PLAYER_RADIUS - WALL_SKIN;
+    let start = Vec3::new(t.translation.x, head_y - radius, t.translation.z);
+    let options = ShapeCastOptions {
+        max_time_of_impact: rise + FOOT_BELOW_FEET,
+        compute_impact_geometry_on_penetration: true,
+        ..default()
+    };
+    let filter = QueryFilter::default().exclude_collider(player_e);
+    let hit = rapier.cast_shape(start, Quat::IDENTITY, Vec3::Y, &Collider::ball(radius), options, filter);
+
+    // witness1 is the contact on the ceiling collider, i.e. its underside
+    let Some(underside) = hit.and_then(|(_, h)| h.details).map(|d| d.witness1.y) else { return; };
+    if underside < head_y - FOOT_BELOW_FEET {
+        return;
+    }
+
+    st.fall_vel_y = 0.0;
+    t.translation.y = t.translation.y.min(underside - half_height - FOOT_BELOW_FEET);
+}
+
+/// While crouched, probe the slab the body would grow into when standing; anything
+/// there keeps the player crouched (movement_system reads `crouch_blocked`).
+pub fn check_crouch_headroom(
+    rapier: Res<RapierContext>,
+    dims: Res<PlayerDimensions>,
+    mut st: ResMut<MovementState>,
+    player_q: Query<(Entity, &Transform), With<Player>>,
+) {
+    let Ok((player_e, t)) = player_q.get_single() else { return; };
+
+    let crouch_half = dims.current_half_height(true);
+    let grow_half = dims.half_height - crouch_half;
+    if !st.is_crouching || grow_half <= 0.0 {
+        st.crouch_blocked = false;
+        return;
+    }
+
+    // from the crouched head up to where the standing head would be
+    let head_half = dims.foot_half_extents();
+    let center = t.translation + Vec3::Y * (crouch_half + grow_half);
+    let slab = Collider::cuboid(head_half.x, grow_half, head_half.z);
+
+    st.crouch_blocked = rapier
+        .intersection_with_shape(center, Quat::IDENTITY, &slab, QueryFilter::default().exclude_collider(player_e))
+        .is_some();
+}
+
+/// Counts air_time and records launch/peak height while airborne; on touchdown sends
+/// Landed and AirtimeStats and shakes the camera on hard ones. Runs after grounding
+/// for both controllers, while fall_vel_y still holds the impact speed.
+#[allow(clippy::too_many_arguments)]
+pub fn track_airtime(
+    time: Res<Time>,
+    mut was_falling: Local<bool>,
+    mut grounded_y: Local<Option<f32>>,
+    trauma: Res<CameraTrauma>,
+    mut st: ResMut<MovementState>,
+    mut stats: EventWriter<AirtimeStats>,
+    mut landings: EventWriter<Landed>,
+    mut shakes: EventWriter<CameraShake>,
+    player_q: Query<&Transform, With<Player>>,
+) {
+    let Ok(t) = player_q.get_single() else { return; };
+    let y = t.translation.y;
+
+    match (*was_falling, st.is_falling) {
+        (false, true) => {
+            // this tick already moved us off the ground, so measure from where we stood
+            st.launch_y = grounded_y.unwrap_or(y);
+            st.peak_y = y;
+            st.air_time = time.delta_seconds();
+        }
+        (true, true) => {
+            st.peak_y = st.peak_y.max(y);
+            st.air_time += time.delta_seconds();
+        }
+        (true, false) => {
+            // fall_vel_y is only reset by the next movement tick
+            let impact_speed = (-st.fall_vel_y).max(0.0);
+            landings.send(Landed { impact_speed, position: st.ground_point });
+            stats.send(AirtimeStats {
+                airtime: st.air_time,
+                peak_height: (st.peak_y - st.launch_y).max(0.0),
+                impact_speed,
+            });
+
+            let shake = trauma.landing(impact_speed);
+            if shake > 0.0 {
+                shakes.send(CameraShake { trauma: shake });
+            }
+            st.air_time = 0.0;
+        }
+        (false, false) => {
+            st.air_time = 0.0;
+        }
+    }
+
+    if !st.is_falling {
+        *grounded_y = Some(y);
+    }
+    *was_falling = st.is_falling;
+}
+
+/// Safety net: if we've been airborne suspiciously long (air_time, from track_airtime)
+/// with Ground right at (or inside) the feet, grounding missed it; land on it. A long
+/// fall with nothing close underneath is left alone.
+pub fn recover_stuck_airborne(
+    rapier: Res<RapierContext>,
+    dims: Res<PlayerDimensions>,
+    mask: Res<PlayerGroundMask>,
+    mut st: ResMut<MovementState>,
+    mut landed: EventWriter<Landed>,
+    ground_q: Query<(), With<Ground>>,
+    mut player_q: Query<(Entity, &mut Transform), With<Player>>,
+) {
+    if !st.is_falling || st.air_time <= st.max_air_time {
+        return;
+    }
+
+    let Ok((player_e, mut t)) = player_q.get_single_mut() else { return; };
+    let pos = t.translation;
+
+    let is_ground = |e| ground_q.contains(e);
+    let filter = QueryFilter::default()
+        .exclude_collider(player_e)
+        .groups(mask.0)
+        .predicate(&is_ground);
+
+    // solid ray: starting inside the ground hits at 0
+    let half_height = dims.current_half_height(st.is_crouching);
+    let reach = half_height + AIR_RECOVERY_REACH;
+    let Some((_, toi)) = rapier.cast_ray(pos, Vec3::NEG_Y, reach, true, filter) else {
+        return;
+    };
+
+    warn!("player airborne for {:.1}s, recovering onto ground {:.2} below", st.air_time, toi);
+
+    let ground_y = pos.y - toi;
+    t.translation.y = ground_y + half_height;
+    // still a landing: fall damage, rumble etc. see the real impact speed
+    landed.send(Landed {
+        impact_speed: (-st.fall_vel_y).max(0.0),
+        position: Vec3::new(pos.x, ground_y, pos.z),
+    });
+    st.is_falling = false;
+    st.fall_vel_y = 0.0;
+    st.air_time = 0.0;
+}
+
+pub fn toggle_cursor_capture(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>
+) {
+    if !keys.just_pressed(CURSOR_CAPTURE_KEY) {
+        return;
+    }
+
+    let Ok(mut window) = windows.get_single_mut() else {
+        return;
+    };
+
+    let capture = window.cursor.grab_mode == CursorGrabMode::None;
+    window.cursor.grab_mode = if capture { CursorGrabMode::Locked } else { CursorGrabMode::None };
+    window.cursor.visible = !capture;
+}
+
+/// While the cursor is captured, mouse motion orbits the follow camera around the
+/// player (movement follows via sync_camera_yaw).
+pub fn orbit_camera_with_mouse(
+    mut motion: EventReader<MouseMotion>,
+    settings: Res<CameraFollowSettings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut cam_q: Query<&mut FollowPlayerCamera>
+) {
+    let delta: Vec2 = motion.read().map(|m| m.delta).sum();
+
+    let captured = windows.get_single().is_ok_and(|w| w.cursor.grab_mode != CursorGrabMode::None);
+    if !captured || delta == Vec2::ZERO {
+        return;
+    }
+
+    let Ok(mut cam) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    // mouse right turns the view right; mouse up looks up (camera drops)
+    cam.yaw += delta.x * settings.mouse_sensitivity;
+    cam.pitch = (cam.pitch + delta.y * settings.mouse_sensitivity)
+        .clamp(settings.min_pitch, settings.max_pitch);
+}
+
+/// Switches CameraMode and hides the player mesh while in first person.
+pub fn toggle_camera_mode(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut mode: ResMut<CameraMode>,
+    mut player_q: Query<&mut Visibility, With<Player>>
+) {
+    if !keys.just_pressed(CAMERA_MODE_KEY) {
+        return;
+    }
+
+    *mode = match *mode {
+        CameraMode::ThirdPerson => CameraMode::FirstPerson,
+        CameraMode::FirstPerson => CameraMode::ThirdPerson,
+    };
+
+    if let Ok(mut visibility) = player_q.get_single_mut() {
+        *visibility = if *mode == CameraMode::FirstPerson { Visibility::Hidden } else { Visibility::Inherited };
+    }
+}
+
+/// Adds CameraShake trauma, decays it and shakes the camera through the kick and roll
+/// springs: each frame pushes them toward the shake offset, so a shake settles the same
+/// way a dash kick does.
+pub fn shake_camera(
+    time: Res<Time>,
+    mut shakes: EventReader<CameraShake>,
+    mut trauma: ResMut<CameraTrauma>,
+    mut kick: ResMut<CameraKick>,
+    mut roll: ResMut<CameraRoll>
+) {
+    let added: f32 = shakes.read().map(|s| s.trauma.max(0.0)).sum();
+    if added == 0.0 && trauma.trauma == 0.0 {
+        return;
+    }
+
+    let dt = time.delta_seconds();
+    let decayed = (trauma.trauma - trauma.decay_per_s * dt).max(0.0);
+    trauma.trauma = (decayed + added).min(1.0);
+
+    // spring force toward the shake offset, as if it were the spring's rest point
+    let (offset, angle) = trauma.shake(time.elapsed_seconds());
+    let kick_push = offset * kick.stiffness * dt;
+    kick.impulse(kick_push);
+    let roll_push = angle * roll.stiffness * dt;
+    roll.impulse(roll_push);
+}
+
+pub fn zoom_camera_with_wheel(
+    time: Res<Time>,
+    mut wheel: EventReader<MouseWheel>,
+    settings: Res<CameraFollowSettings>,
+    mut zoom: ResMut<CameraZoom>
+) {
+    let notches: f32 = wheel
+        .read()
+        .map(|w| match w.unit {
+            MouseScrollUnit::Line => w.y,
+            MouseScrollUnit::Pixel => w.y / SCROLL_PIXELS_PER_LINE,
+        })
+        .sum();
+
+    // never let the zoom go inside the camera-collision minimum
+    let min = zoom.min.max(settings.min_camera_distance);
+    let max = zoom.max.max(min);
+
+    // scrolling up (away from you) zooms in
+    let target = (zoom.target - notches * zoom.step).clamp(min, max);
+    let k = if zoom.smoothing > 0.0 { 1.0 - (-zoom.smoothing * time.delta_seconds()).exp() } else { 1.0 };
+    let mut current = zoom.current + (target - zoom.current) * k;
+    // settle exactly so we stop touching the resource
+    if (target - current).abs() < 1e-3 {
+        current = target;
+    }
+    if zoom.target != target || zoom.current != current {
+        zoom.target = target;
+        zoom.current = current;
+    }
+}
+
+/// Below the kill plane: back to PlayerSpawn with all motion cleared, and the
+/// camera snaps instead of easing across the map.
+pub fn respawn_below_kill_plane(
+    kill_plane: Res<KillPlane>,
+    spawn: Res<PlayerSpawn>,
+    mut st: ResMut<MovementState>,
+    mut respawned: EventWriter<Respawned>,
+    mut player_q: Query<&mut Transform, With<Player>>,
+    mut cam_q: Query<&mut FollowPlayerCamera>
+) {
+    let Ok(mut t) = player_q.get_single_mut() else { return; };
+    if t.translation.y >= kill_plane.y {
+        return;
+    }
+
+    st.reset_motion();
+    st.is_falling = false;
+    st.is_jumping = false;
+    // don't get carried by a platform we stood on before falling
+    st.standing_on = None;
+    st.dir = spawn.facing.normalize_or(Vec2::Y);
+
+    t.translation = spawn.position;
+    t.rotation = facing_rotation(st.dir);
+
+    if let Ok(mut cam) = cam_q.get_single_mut() {
+        cam.reset_smoothing();
+    }
+
+    respawned.send(Respawned { position: spawn.position });
+}
+
+pub fn sync_camera_yaw(cam_q: Query<&FollowPlayerCamera>, mut st: ResMut<MovementState>) {
+    let Ok(cam) = cam_q.get_single() else {
+        return;
+    };
+
+    if st.camera_yaw != cam.yaw {
+        st.camera_yaw = cam.yaw;
+    }
+}
+
+pub fn stabilize_camera_roll(time: Res<Time>, mut roll: ResMut<CameraRoll>) {
+    if roll.angle == 0.0 && roll.velocity == 0.0 {
+        return;
+    }
+
+    let dt = time.delta_seconds();
+
+    // semi-implicit Euler on a damped spring toward 0
+    let accel = -roll.stiffness * roll.angle - roll.damping * roll.velocity;
+    roll.velocity += accel * dt;
+    roll.angle += roll.velocity * dt;
+
+    // settle exactly so we stop touching the resource
+    if roll.angle.abs() < 1e-4 && roll.velocity.abs() < 1e-3 {
+        roll.angle = 0.0;
+        roll.velocity = 0.0;
+    }
+}
+
+/// On the tick a dash starts, kick the camera back against the dash direction.
+pub fn kick_camera_on_dash(
+    mut was_dashing: Local<bool>,
+    st: Res<MovementState>,
+    mut kick: ResMut<CameraKick>
+) {
+    let dashing = st.dash_timer > 0.0;
+    if dashing && !*was_dashing && kick.dash_intensity != 0.0 {
+        let dir = st.dir.normalize_or_zero();
+        let intensity = kick.dash_intensity;
+        kick.impulse(-Vec3::new(dir.x, 0.0, dir.y) * intensity);
+    }
+    *was_dashing = dashing;
+}
+
+pub fn settle_camera_kick(time: Res<Time>, mut kick: ResMut<CameraKick>) {
+    if kick.offset == Vec3::ZERO && kick.velocity == Vec3::ZERO {
+        return;
+    }
+
+    let dt = time.delta_seconds();
+
+    // same damped spring as the roll, per axis
+    let accel = -kick.stiffness * kick.offset - kick.damping * kick.velocity;
+    kick.velocity += accel * dt;
+    let v = kick.velocity;
+    kick.offset += v * dt;
+
+    if kick.offset.length() < 1e-4 && kick.velocity.length() < 1e-3 {
+        kick.offset = Vec3::ZERO;
+        kick.velocity = Vec3::ZERO;
+    }
+}
+
+// Exponential ease of `current` toward `goal` at `rate` (1/s); no current or rate = snap.
+fn ease_toward(current: Option<Vec3>, goal: Vec3, rate: f32, dt: f32) -> Vec3 {
+    match current {
+        Some(prev) if rate > 0.0 => prev.lerp(goal, 1.0 - (-rate * dt).exp()),
+        _ => goal,
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn follow_player_camera(
+    time: Res<Time>,
+    rapier: Res<RapierContext>,
+    settings: Res<CameraFollowSettings>,
+    roll: Res<CameraRoll>,
+    kick: Res<CameraKick>,
+    zoom: Res<CameraZoom>,
+    mode: Res<CameraMode>,
+    player_q: Query<(Entity, &Transform), With<Player>>,
+    targets: Query<&GlobalTransform>,
+    mut cam_q: Query<(&mut FollowPlayerCamera, &mut Transform, Option<&FrameTarget>), Without<Player>>
+) {
+    let Ok((player_e, player_t)) = player_q.get_single() else {
+        return;
+    };
+    let Ok((mut cam, mut cam_t, frame_target)) = cam_q.get_single_mut() else {
+        return;
+    };
+
+    if *mode == CameraMode::FirstPerson {
+        // look along the direction the third-person camera would face
+        let eye = player_t.translation + Vec3::Y * FIRST_PERSON_EYE_OFFSET;
+        cam_t.translation = eye + kick.offset;
+        cam_t.rotation = Quat::from_rotation_y(-cam.yaw) * Quat::from_rotation_x(-cam.pitch);
+        cam_t.rotate_local_z(roll.angle);
+
+        // snap back into place when returning to third person
+        cam.reset_smoothing();
+        return;
+    }
+
+    // framing a target: follow the midpoint instead and back off to fit both
+    let target_pos = frame_target.and_then(|f| targets.get(f.0).ok()).map(|gt| gt.translation());
+    let (mut player_pos, distance) = match target_pos {
+        Some(target) => frame_player_and_target(&settings, zoom.current, player_t.translation, target),
+        None => (player_t.translation, zoom.current),
+    };
+
+    // ease only the vertical component (first frame snaps)
+    let k = 1.0 - (-settings.camera_vertical_lerp * time.delta_seconds()).exp();
+    let y = match cam.smoothed_y {
+        Some(prev) => prev + (player_pos.y - prev) * k,
+        None => player_pos.y,
+    };
+    cam.smoothed_y = Some(y);
+    player_pos.y = y;
+
+    // deadzone in the camera's horizontal frame: push the focus just enough
+    // to keep the player on the box edge
+    let player_xz = player_pos.xz();
+    let mut focus = cam.focus_xz.unwrap_or(player_xz);
+    let rot = Mat2::from_angle(-cam.yaw); // world xz -> (camera right, camera back)
+    let local = rot * (player_xz - focus);
+    let excess = local - local.clamp(-settings.deadzone, settings.deadzone);
+    focus += rot.transpose() * excess;
+    cam.focus_xz = Some(focus);
+    player_pos.x = focus.x;
+    player_pos.z = focus.y;
+
+    let mut offset = camera_offset(cam.yaw, cam.pitch, distance);
+
+    // don't sit inside walls: pull in to just before the first hit behind the player
+    let length = offset.length();
+    if length > 0.001 {
+        let filter = QueryFilter::default().exclude_collider(player_e);
+        if let Some((_, toi)) = rapier.cast_ray(player_pos, offset / length, length, true, filter) {
+            let distance = (toi - CAMERA_COLLISION_MARGIN).max(settings.min_camera_distance);
+            offset *= distance.min(length) / length;
+        }
+    }
+
+    // ease toward the goal; the first frame snaps so we don't fly in from the origin
+    let dt = time.delta_seconds();
+    let translation = ease_toward(cam.smoothed_translation, player_pos + offset, settings.position_smoothing, dt);
+    let look = ease_toward(cam.smoothed_look, player_pos, settings.look_smoothing, dt);
+    cam.smoothed_translation = Some(translation);
+    cam.smoothed_look = Some(look);
+
+    cam_t.translation = translation;
+    cam_t.look_at(look, Vec3::Y);
+    cam_t.rotate_local_z(roll.angle);
+
+    // translate after aiming so the kick reads as the camera being shoved, not turning
+    cam_t.translation += kick.offset;
+}
+
+/// First person: cast from the camera through the screen center into ReticleTarget.
+pub fn aim_reticle(
+    rapier: Res<RapierContext>,
+    mode: Res<CameraMode>,
+    mut target: ResMut<ReticleTarget>,
+    player_q: Query<Entity, With<Player>>,
+    cam_q: Query<&Transform, With<FollowPlayerCamera>>
+) {
+    let cam_t = cam_q.get_single().ok().filter(|_| *mode == CameraMode::FirstPerson);
+    let Some(cam_t) = cam_t else {
+        if target.hit.is_some() {
+            target.hit = None;
+        }
+        return;
+    };
+
+    let mut filter = QueryFilter::default();
+    if let Ok(player_e) = player_q.get_single() {
+        filter = filter.exclude_collider(player_e);
+    }
+
+    target.hit = rapier
+        .cast_ray_and_get_normal(cam_t.translation, *cam_t.forward(), RETICLE_RANGE, true, filter)
+        .map(|(_, hit)| (hit.point, hit.normal));
+}
+
+/// Small ring on the surface under the reticle.
+fn draw_reticle_marker(mut gizmos: Gizmos, target: Res<ReticleTarget>) {
+    let Some((point, normal)) = target.hit else {
+        return;
+    };
+
+    let normal = Dir3::new(normal).unwrap_or(Dir3::Y);
+    // lifted a hair off the surface to avoid z-fighting
+    gizmos.circle(point + *normal * 0.01, normal, RETICLE_MARKER_RADIUS, Color::srgb(1.0, 0.2, 0.2));
+}
+
+pub fn fade_occluded_player(
+    time: Res<Time>,
+    rapier: Res<RapierContext>,
+    fade: Res<OcclusionFade>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    cam_q: Query<&GlobalTransform, With<FollowPlayerCamera>>,
+    player_q: Query<(Entity, &GlobalTransform, &Handle<StandardMaterial>), With<Player>>
+) {
+    let Ok(cam_gt) = cam_q.get_single() else {
+        return;
+    };
+    let Ok((player_e, player_gt, mat_h)) = player_q.get_single() else {
+        return;
+    };
+
+    let from = cam_gt.translation();
+    let to_player = player_gt.translation() - from;
+    let dist = to_player.length();
+
+    // anything hit before reaching the player is an occluder
+    let occluded = fade.enabled
+        && dist > 0.001
+        && rapier
+            .cast_ray(
+                from,
+                to_player / dist,
+                dist - 0.01,
+                true,
+                QueryFilter::default().exclude_collider(player_e),
+            )
+            .is_some();
+
+    let target = if occluded { fade.occluded_alpha } else { 1.0 };
+
+    let Some(current) = materials.get(mat_h).map(|m| m.base_color.alpha()) else {
+        return;
+    };
+    if current == target {
+        return;
+    }
+
+    let step = fade.fade_speed * time.delta_seconds();
+    let alpha = current + (target - current).clamp(-step, step);
+
+    let Some(mat) = materials.get_mut(mat_h) else {
+        return;
+    };
+    mat.base_color.set_alpha(alpha);
+    mat.alpha_mode = if alpha < 1.0 { AlphaMode::Blend } else { AlphaMode::Opaque };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::InputFrame;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    const DT: f32 = 1.0 / 64.0;
+
+    // Rapier without rendering; colliders spawned before `settle` are queryable after it.
+    fn physics_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, TransformPlugin, AssetPlugin::default()));
+        app.init_asset::<Mesh>();
+        app.add_plugins(bevy::scene::ScenePlugin);
+        app.add_plugins(RapierPhysicsPlugin::<NoUserData>::default());
+        app.init_resource::<PlayerDimensions>();
+        app.init_resource::<MovementState>();
+        app
+    }
+
+    fn fixed_time() -> Time {
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(DT));
+        time
+    }
+
+    fn settle(app: &mut App) {
+        app.update();
+        app.update();
+
+        // systems run by hand below see a fixed tick
+        app.world_mut().insert_resource(fixed_time());
+    }
+
+    fn spawn_block(app: &mut App, center: Vec3, half: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((
+                TransformBundle::from_transform(Transform::from_translation(center)),
+                Collider::cuboid(half.x, half.y, half.z),
+                Ground,
+            ))
+            .id()
+    }
+
+    fn spawn_player(app: &mut App, pos: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((Player, TransformBundle::from_transform(Transform::from_translation(pos))))
+            .id()
+    }
+
+    fn player_y(app: &App, player: Entity) -> f32 {
+        app.world().get::<Transform>(player).unwrap().translation.y
+    }
+
+    fn rising(app: &mut App, fall_vel_y: f32) {
+        let mut st = app.world_mut().resource_mut::<MovementState>();
+        st.is_falling = true;
+        st.is_jumping = true;
+        st.fall_vel_y = fall_vel_y;
+    }
+
+    #[test]
+    fn rapier_controller_counts_air_time_and_lands() {
+        let mut world = World::new();
+        world.insert_resource(fixed_time());
+        world.init_resource::<MovementState>();
+        world.init_resource::<PlayerDimensions>();
+        world.init_resource::<CameraTrauma>();
+        world.init_resource::<Events<AirtimeStats>>();
+        world.init_resource::<Events<Landed>>();
+        world.init_resource::<Events<CameraShake>>();
+        let player = world
+            .spawn((
+                Player,
+                Transform::from_xyz(1.0, PLAYER_HALF_HEIGHT, 2.0),
+                KinematicCharacterControllerOutput::default(),
+            ))
+            .id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((read_character_controller, track_airtime).chain());
+
+        // walked off an edge: airborne for 10 ticks
+        for _ in 0..10 {
+            schedule.run(&mut world);
+        }
+        let st = world.resource::<MovementState>();
+        assert!(st.is_falling);
+        assert!((st.air_time - 10.0 * DT).abs() < 1e-5);
+
+        world.resource_mut::<MovementState>().fall_vel_y = -15.0;
+        world.get_mut::<KinematicCharacterControllerOutput>(player).unwrap().grounded = true;
+        schedule.run(&mut world);
+
+        let landed: Vec<Landed> = world.resource_mut::<Events<Landed>>().drain().collect();
+        assert_eq!(landed.len(), 1);
+        assert_eq!(landed[0].impact_speed, 15.0);
+        assert_eq!(landed[0].position, Vec3::new(1.0, 0.0, 2.0));
+
+        let stats: Vec<AirtimeStats> = world.resource_mut::<Events<AirtimeStats>>().drain().collect();
+        assert_eq!(stats.len(), 1);
+        assert!((stats[0].airtime - 10.0 * DT).abs() < 1e-5);
+
+        // hard enough to shake the camera
+        assert_eq!(world.resource::<Events<CameraShake>>().len(), 1);
+        assert_eq!(world.resource::<MovementState>().air_time, 0.0);
+    }
+
+    #[test]
+    fn standing_on_a_boost_pad_runs_its_effect() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.init_resource::<GroundEffects>();
+        app.world_mut().resource_mut::<GroundEffects>().register::<BoostPad>(boost_pad_effect);
+        let pad = spawn_block(&mut app, Vec3::ZERO, Vec3::new(5.0, 0.25, 5.0));
+        app.world_mut().entity_mut(pad).insert(BoostPad { direction: Vec2::X, speed: 20.0 });
+        spawn_player(&mut app, Vec3::new(0.0, 0.25 + PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((update_grounded_flag_and_snap, apply_ground_effects).chain());
+        schedule.run(app.world_mut());
+
+        let st = app.world().resource::<MovementState>();
+        assert_eq!(st.standing_on, Some(pad));
+        assert_eq!(st.speed, 20.0);
+        assert_eq!(st.dir, Vec2::X);
+    }
+
+    #[test]
+    fn custom_grounding_lands_at_the_hit_point() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.init_resource::<CameraTrauma>();
+        app.add_event::<AirtimeStats>();
+        app.add_event::<Landed>();
+        app.add_event::<CameraShake>();
+        // top at y = 0.25
+        spawn_block(&mut app, Vec3::ZERO, Vec3::new(5.0, 0.25, 5.0));
+        spawn_player(&mut app, Vec3::new(1.0, 2.0, -1.0));
+        settle(&mut app);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((update_grounded_flag_and_snap, track_airtime).chain());
+        schedule.run(app.world_mut());
+        assert!(app.world().resource::<MovementState>().is_falling);
+
+        // sunk a little into the block while falling
+        let world = app.world_mut();
+        let mut q = world.query_filtered::<&mut Transform, With<Player>>();
+        q.single_mut(world).translation.y = 0.7;
+        world.resource_mut::<MovementState>().fall_vel_y = -8.0;
+        schedule.run(world);
+
+        let landed: Vec<Landed> = world.resource_mut::<Events<Landed>>().drain().collect();
+        assert_eq!(landed.len(), 1);
+        assert_eq!(landed[0].impact_speed, 8.0);
+        assert!(landed[0].position.distance(Vec3::new(1.0, 0.25, -1.0)) < 1e-3, "{:?}", landed[0].position);
+    }
+
+    // grounded when standing `overhang` past the +X edge of a floor with its top at y = 0
+    fn grounded_past_edge(ledge_tolerance: f32, overhang: f32) -> bool {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.world_mut().resource_mut::<PlayerDimensions>().ledge_tolerance = ledge_tolerance;
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        spawn_player(&mut app, Vec3::new(5.0 + overhang, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+
+        app.world_mut().run_system_once(update_grounded_flag_and_snap);
+        !app.world().resource::<MovementState>().is_falling
+    }
+
+    #[test]
+    fn ledge_tolerance_widens_the_footprint() {
+        let overhang = PLAYER_RADIUS + 0.2;
+        assert!(grounded_past_edge(0.0, PLAYER_RADIUS - 0.05));
+        assert!(!grounded_past_edge(0.0, overhang));
+        assert!(grounded_past_edge(0.3, overhang));
+        // negative tolerance narrows it
+        assert!(!grounded_past_edge(-0.1, PLAYER_RADIUS - 0.05));
+    }
+
+    #[test]
+    fn dash_kicks_the_camera_back_against_it() {
+        let mut world = World::new();
+        world.insert_resource(fixed_time());
+        world.init_resource::<MovementState>();
+        // dashes go along the current facing
+        world.resource_mut::<MovementState>().dir = Vec2::X;
+        world.init_resource::<Events<ScriptedMove>>();
+        world.init_resource::<CameraKick>();
+        world.insert_resource(InputFrame { move_dir: Vec2::X, dash: true, ..default() });
+        let mut schedule = Schedule::default();
+        schedule.add_systems((movement_system, kick_camera_on_dash).chain());
+
+        schedule.run(&mut world);
+        let kick = world.resource::<CameraKick>();
+        assert!(world.resource::<MovementState>().dash_timer > 0.0);
+        assert_eq!(kick.velocity, Vec3::NEG_X * kick.dash_intensity);
+
+        // once per dash, not every dashing tick
+        *world.resource_mut::<InputFrame>() = InputFrame { move_dir: Vec2::X, ..default() };
+        schedule.run(&mut world);
+        let kick = world.resource::<CameraKick>();
+        assert_eq!(kick.velocity, Vec3::NEG_X * kick.dash_intensity);
+
+        // the spring swings the camera back and settles
+        let mut furthest = 0.0_f32;
+        for _ in 0..128 {
+            world.run_system_once(settle_camera_kick);
+            furthest = furthest.min(world.resource::<CameraKick>().offset.x);
+        }
+        assert!(furthest < 0.0);
+        assert_eq!(world.resource::<CameraKick>().offset, Vec3::ZERO);
+    }
+
+    #[test]
+    fn walking_up_a_30_degree_ramp_stays_grounded() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        let slope = 30f32.to_radians();
+        // rises toward +X; its top passes through (0, 0.5 / cos 30)
+        let ramp = spawn_block(&mut app, Vec3::ZERO, Vec3::new(10.0, 0.5, 3.0));
+        app.world_mut().get_mut::<Transform>(ramp).unwrap().rotation = Quat::from_rotation_z(slope);
+        let player = spawn_player(&mut app, Vec3::new(0.0, 0.6 + PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+        app.world_mut().run_system_once(update_grounded_flag_and_snap);
+        assert!(!app.world().resource::<MovementState>().is_falling);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((apply_player_motion, update_grounded_flag_and_snap).chain());
+        app.world_mut().resource_mut::<MovementState>().velocity = Vec2::new(3.0, 0.0);
+        let start = app.world().get::<Transform>(player).unwrap().translation;
+        let mut last_y = start.y;
+        for _ in 0..64 {
+            schedule.run(app.world_mut());
+            let st = app.world().resource::<MovementState>();
+            assert!(!st.is_falling);
+            assert!(st.ground_normal.distance(Vec3::new(-slope.sin(), slope.cos(), 0.0)) < 1e-4);
+            let y = player_y(&app, player);
+            assert!(y > last_y);
+            last_y = y;
+        }
+
+        let moved = app.world().get::<Transform>(player).unwrap().translation - start;
+        assert!((moved.x - 3.0).abs() < 1e-3, "{moved}");
+        assert!((moved.y - 3.0 * slope.tan()).abs() < 1e-2, "{moved}");
+    }
+
+    // one tick of a fast dive across a thin platform (top at y = 0, 0.1 thick);
+    // returns whether it ended grounded on it
+    fn dive_onto_thin_platform(movement_substeps: u32) -> bool {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        spawn_block(&mut app, Vec3::new(1.5, -0.05, 0.0), Vec3::new(2.5, 0.05, 2.0));
+        let player = spawn_player(&mut app, Vec3::new(0.0, 0.5 + PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+        let mut st = app.world_mut().resource_mut::<MovementState>();
+        st.movement_substeps = movement_substeps;
+        st.is_falling = true;
+        // 2 units across and 3 down in a tick: the whole body passes the platform
+        st.velocity = Vec2::new(128.0, 0.0);
+        st.fall_vel_y = -192.0;
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((apply_player_motion, update_grounded_flag_and_snap).chain());
+        schedule.run(app.world_mut());
+
+        let pos = app.world().get::<Transform>(player).unwrap().translation;
+        assert!((pos.x - 2.0).abs() < 1e-4);
+        let grounded = !app.world().resource::<MovementState>().is_falling;
+        assert_eq!(grounded, (pos.y - PLAYER_HALF_HEIGHT).abs() < 1e-4, "{pos}");
+        grounded
+    }
+
+    #[test]
+    fn substeps_stop_fast_motion_tunnelling_through_thin_ground() {
+        assert!(!dive_onto_thin_platform(1));
+        assert!(dive_onto_thin_platform(16));
+    }
+
+    // airborne past max_air_time with the body center `height` above a floor topped at y = 0
+    fn stuck_airborne_at(height: f32) -> (App, Entity) {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.add_event::<Landed>();
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        let player = spawn_player(&mut app, Vec3::new(1.0, height, 0.0));
+        settle(&mut app);
+        let mut st = app.world_mut().resource_mut::<MovementState>();
+        st.is_falling = true;
+        st.fall_vel_y = -7.0;
+        st.air_time = st.max_air_time + 0.1;
+
+        app.world_mut().run_system_once(recover_stuck_airborne);
+        (app, player)
+    }
+
+    #[test]
+    fn stuck_airborne_player_snaps_onto_ground_at_the_feet() {
+        // sunk into the floor, where grounding should have caught it
+        let (mut app, player) = stuck_airborne_at(PLAYER_HALF_HEIGHT - 0.2);
+
+        assert!((player_y(&app, player) - PLAYER_HALF_HEIGHT).abs() < 1e-4);
+        let st = app.world().resource::<MovementState>();
+        assert!(!st.is_falling);
+        assert_eq!(st.air_time, 0.0);
+        let landed: Vec<Landed> = app.world_mut().resource_mut::<Events<Landed>>().drain().collect();
+        assert_eq!(landed.len(), 1);
+        assert_eq!(landed[0].impact_speed, 7.0);
+    }
+
+    #[test]
+    fn long_fall_is_not_recovered() {
+        let (app, player) = stuck_airborne_at(20.0);
+
+        assert_eq!(player_y(&app, player), 20.0);
+        let st = app.world().resource::<MovementState>();
+        assert!(st.is_falling);
+        assert_eq!(st.fall_vel_y, -7.0);
+        assert!(app.world().resource::<Events<Landed>>().is_empty());
+    }
+
+    #[test]
+    fn overlapping_ground_snaps_to_the_highest_top() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        // floor top at y = 0, a step sunk into it with its top at y = 1
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        spawn_block(&mut app, Vec3::new(0.0, 0.25, 0.0), Vec3::new(1.0, 0.75, 1.0));
+        let player = spawn_player(&mut app, Vec3::new(0.0, 1.0 + PLAYER_HALF_HEIGHT - 0.1, 0.0));
+        settle(&mut app);
+
+        app.world_mut().run_system_once(update_grounded_flag_and_snap);
+
+        assert!(!app.world().resource::<MovementState>().is_falling);
+        let y = player_y(&app, player);
+        assert!((y - (1.0 + PLAYER_HALF_HEIGHT)).abs() < 1e-3, "{y}");
+    }
+
+    #[test]
+    fn shake_moves_the_camera_through_the_kick_and_roll_springs() {
+        let mut world = World::new();
+        world.insert_resource(fixed_time());
+        world.init_resource::<CameraTrauma>();
+        world.init_resource::<CameraKick>();
+        world.init_resource::<CameraRoll>();
+        world.init_resource::<Events<CameraShake>>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((shake_camera, (stabilize_camera_roll, settle_camera_kick)).chain());
+
+        // no trauma: springs stay at rest
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<CameraKick>().offset, Vec3::ZERO);
+        assert_eq!(world.resource::<CameraRoll>().angle, 0.0);
+
+        world.send_event(CameraShake { trauma: 1.0 });
+        let mut max_offset = 0.0_f32;
+        let mut max_roll = 0.0_f32;
+        for _ in 0..32 {
+            // the noise is sampled from elapsed time
+            world.resource_mut::<Time>().advance_by(Duration::from_secs_f32(DT));
+            schedule.run(&mut world);
+            max_offset = max_offset.max(world.resource::<CameraKick>().offset.length());
+            max_roll = max_roll.max(world.resource::<CameraRoll>().angle.abs());
+        }
+        let trauma = world.resource::<CameraTrauma>();
+        assert!(max_offset > 0.0 && max_offset < trauma.position_amplitude * 2.0, "{max_offset}");
+        assert!(max_roll > 0.0 && max_roll < trauma.rotation_amplitude * 2.0, "{max_roll}");
+    }
+
+    #[test]
+    fn first_person_reticle_marks_the_ground_it_points_at() {
+        let mut app = physics_app();
+        app.init_resource::<CameraMode>();
+        app.init_resource::<ReticleTarget>();
+        // top at y = 0
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(10.0, 0.5, 10.0));
+        let eye = Vec3::new(1.0, 2.0, 1.0);
+        let aim = Vec3::new(1.0, 0.0, -2.0);
+        app.world_mut().spawn((
+            FollowPlayerCamera::default(),
+            Transform::from_translation(eye).looking_at(aim, Vec3::Y),
+        ));
+        settle(&mut app);
+
+        app.world_mut().run_system_once(aim_reticle);
+        assert!(app.world().resource::<ReticleTarget>().hit.is_none());
+
+        *app.world_mut().resource_mut::<CameraMode>() = CameraMode::FirstPerson;
+        app.world_mut().run_system_once(aim_reticle);
+        let (point, normal) = app.world().resource::<ReticleTarget>().hit.unwrap();
+        assert!(point.distance(aim) < 1e-3, "{point}");
+        assert!(normal.distance(Vec3::Y) < 1e-3, "{normal}");
+    }
+
+    // runs setup_player with default settings changed by `configure`
+    fn set_up_player(configure: impl FnOnce(&mut World)) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+        app.init_asset::<Mesh>();
+        app.init_asset::<StandardMaterial>();
+        app.init_asset::<Image>();
+        app.init_resource::<SkyboxSettings>();
+        app.init_resource::<PlayerSpawn>();
+        app.init_resource::<PlayerController>();
+        app.init_resource::<ListenerMode>();
+        app.init_resource::<MovementState>();
+        configure(app.world_mut());
+        app.world_mut().run_system_once(setup_player);
+        app
+    }
+
+    #[test]
+    fn setup_player_faces_the_spawn_with_the_camera_behind() {
+        let position = Vec3::new(3.0, 1.0, -2.0);
+        let mut app = set_up_player(|world| {
+            world.insert_resource(PlayerSpawn { position, facing: Vec2::new(2.0, 0.0) });
+        });
+
+        let st = app.world().resource::<MovementState>();
+        assert_eq!(st.dir, Vec2::X);
+        let yaw = camera_yaw_behind(Vec2::X);
+        assert_eq!(st.camera_yaw, yaw);
+
+        let world = app.world_mut();
+        let player_t = *world.query_filtered::<&Transform, With<Player>>().single(world);
+        assert_eq!(player_t.translation, position);
+        assert_eq!(player_t.rotation, facing_rotation(Vec2::X));
+
+        let (cam, cam_t) = world.query::<(&FollowPlayerCamera, &Transform)>().single(world);
+        assert_eq!(cam.yaw, yaw);
+        let behind = (cam_t.translation - position).xz().normalize();
+        assert!(behind.distance(Vec2::NEG_X) < 1e-5, "{behind}");
+        assert!((*cam_t.forward()).xz().normalize().distance(Vec2::X) < 1e-5);
+    }
+
+    #[test]
+    fn camera_sits_behind_its_heading() {
+        for yaw in [0.0, 0.7, std::f32::consts::FRAC_PI_2, 2.5, -1.2] {
+            let heading = Vec2::new(f32::sin(yaw), -f32::cos(yaw));
+            let offset = camera_offset(yaw, CAMERA_PITCH, CAMERA_DISTANCE);
+            assert!(offset.xz().normalize().distance(-heading) < 1e-5, "yaw {yaw}: {offset}");
+
+            // first person looks the same way
+            let look = Quat::from_rotation_y(-yaw) * Vec3::NEG_Z;
+            assert!(look.xz().distance(heading) < 1e-5, "yaw {yaw}: {look}");
+        }
+    }
+
+    // a follow camera that snaps its position and aim (only the height eases)
+    fn camera_app(player_pos: Vec3) -> (App, Entity, Entity) {
+        let mut app = physics_app();
+        app.init_resource::<CameraFollowSettings>();
+        app.init_resource::<CameraRoll>();
+        app.init_resource::<CameraKick>();
+        app.init_resource::<CameraZoom>();
+        app.init_resource::<CameraMode>();
+        let mut settings = app.world_mut().resource_mut::<CameraFollowSettings>();
+        settings.position_smoothing = 0.0;
+        settings.look_smoothing = 0.0;
+        let player = spawn_player(&mut app, player_pos);
+        let camera = app
+            .world_mut()
+            .spawn((FollowPlayerCamera::default(), TransformBundle::default()))
+            .id();
+        settle(&mut app);
+        (app, player, camera)
+    }
+
+    fn move_player(app: &mut App, player: Entity, pos: Vec3) {
+        app.world_mut().get_mut::<Transform>(player).unwrap().translation = pos;
+    }
+
+    fn camera_pos(app: &App, camera: Entity) -> Vec3 {
+        app.world().get::<Transform>(camera).unwrap().translation
+    }
+
+    #[test]
+    fn camera_height_eases_up_a_step() {
+        let (mut app, player, camera) = camera_app(Vec3::ZERO);
+        app.world_mut().run_system_once(follow_player_camera);
+        let start = camera_pos(&app, camera);
+
+        // snapped up a 0.3 step while walking 0.1 along X
+        move_player(&mut app, player, Vec3::new(0.1, 0.3, 0.0));
+        let mut schedule = Schedule::default();
+        schedule.add_systems(follow_player_camera);
+        schedule.run(app.world_mut());
+        let pos = camera_pos(&app, camera);
+        let rate = app.world().resource::<CameraFollowSettings>().camera_vertical_lerp;
+        let expected = 0.3 * (1.0 - (-rate * DT).exp());
+        assert!((pos.y - start.y - expected).abs() < 1e-5, "{} vs {expected}", pos.y - start.y);
+        // horizontal follow stays tight
+        assert!((pos.x - start.x - 0.1).abs() < 1e-5);
+
+        let mut last = pos.y;
+        for _ in 0..256 {
+            schedule.run(app.world_mut());
+            let y = camera_pos(&app, camera).y;
+            assert!(y >= last);
+            last = y;
+        }
+        assert!((last - start.y - 0.3).abs() < 1e-3);
+    }
+
+    #[test]
+    fn grounded_player_rides_a_moving_platform() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        let a = Vec3::new(0.0, -0.25, 0.0);
+        let platform = spawn_block(&mut app, a, Vec3::new(1.5, 0.25, 1.5));
+        app.world_mut()
+            .entity_mut(platform)
+            .insert(MovingPlatform::new(a, a + Vec3::X * 6.0, 2.0));
+        let player = spawn_player(&mut app, Vec3::new(0.0, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+        app.world_mut().resource_mut::<MovementState>().standing_on = Some(platform);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((move_platforms, apply_player_motion).chain());
+        for _ in 0..3 {
+            let before = app.world().get::<Transform>(player).unwrap().translation;
+            schedule.run(app.world_mut());
+
+            let delta = app.world().get::<MovingPlatform>(platform).unwrap().delta;
+            let after = app.world().get::<Transform>(player).unwrap().translation;
+            assert!(delta.x > 0.0);
+            assert!((after - before).distance(delta) < 1e-6, "{} vs {delta}", after - before);
+        }
+    }
+
+    #[test]
+    fn respawn_forgets_the_platform() {
+        let mut world = World::new();
+        world.init_resource::<KillPlane>();
+        world.init_resource::<PlayerSpawn>();
+        world.init_resource::<Events<Respawned>>();
+        let platform = world.spawn_empty().id();
+        let mut st = MovementState::default();
+        st.standing_on = Some(platform);
+        world.insert_resource(st);
+        world.spawn((Player, Transform::from_xyz(0.0, -100.0, 0.0)));
+
+        world.run_system_once(respawn_below_kill_plane);
+
+        assert_eq!(world.resource::<MovementState>().standing_on, None);
+        assert_eq!(world.resource::<Events<Respawned>>().len(), 1);
+    }
+
+    #[test]
+    fn head_into_ceiling_stops_the_rise() {
+        let mut app = physics_app();
+        // underside at y = 2.5
+        spawn_block(&mut app, Vec3::new(0.0, 3.0, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        // head was at 2.4 and this tick's rise (10 * DT) pushed it to 2.55
+        let player = spawn_player(&mut app, Vec3::new(0.0, 1.9 + 10.0 * DT, 0.0));
+        settle(&mut app);
+        rising(&mut app, 10.0);
+
+        app.world_mut().run_system_once(stop_at_ceiling);
+
+        assert_eq!(app.world().resource::<MovementState>().fall_vel_y, 0.0);
+        let head = player_y(&app, player) + PLAYER_HALF_HEIGHT;
+        assert!(head < 2.5 && head > 2.45, "head at {head}");
+    }
+
+    #[test]
+    fn walls_and_steps_beside_the_body_are_not_ceilings() {
+        let mut app = physics_app();
+        // wide footprint, like a large ledge_tolerance
+        app.world_mut().resource_mut::<PlayerDimensions>().ledge_tolerance = 0.4;
+        // wall the capsule slides along on +X, step top just below the head on -X
+        spawn_block(&mut app, Vec3::new(PLAYER_RADIUS + WALL_SKIN + 0.5, 2.0, 0.0), Vec3::new(0.5, 3.0, 5.0));
+        spawn_block(&mut app, Vec3::new(-PLAYER_RADIUS - WALL_SKIN - 0.5, 0.5, 0.0), Vec3::new(0.5, 1.5, 5.0));
+        let player = spawn_player(&mut app, Vec3::new(0.0, 1.0, 0.0));
+        settle(&mut app);
+        rising(&mut app, 10.0);
+
+        app.world_mut().run_system_once(stop_at_ceiling);
+
+        assert_eq!(app.world().resource::<MovementState>().fall_vel_y, 10.0);
+        assert_eq!(player_y(&app, player), 1.0);
+    }
+
+    #[test]
+    fn grounded_and_airborne_ticks_count_up_and_reset() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        // top at y = 0, edge at x = 5
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        let player = spawn_player(&mut app, Vec3::new(0.0, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_grounded_flag_and_snap);
+        for n in 1..=3 {
+            schedule.run(app.world_mut());
+            let st = app.world().resource::<MovementState>();
+            assert_eq!((st.grounded_ticks, st.airborne_ticks), (n, 0));
+        }
+
+        // walked off the edge
+        move_player(&mut app, player, Vec3::new(7.0, PLAYER_HALF_HEIGHT, 0.0));
+        for n in 1..=2 {
+            schedule.run(app.world_mut());
+            let st = app.world().resource::<MovementState>();
+            assert_eq!((st.grounded_ticks, st.airborne_ticks), (0, n));
+        }
+    }
+
+    // camera distance from the framed midpoint with the target `spread` along X
+    fn framed_distance(spread: f32) -> f32 {
+        let (mut app, _, camera) = camera_app(Vec3::ZERO);
+        let target = app.world_mut().spawn(GlobalTransform::from_xyz(spread, 0.0, 0.0)).id();
+        app.world_mut().entity_mut(camera).insert(FrameTarget(target));
+        app.world_mut().run_system_once(follow_player_camera);
+        camera_pos(&app, camera).distance(Vec3::new(spread / 2.0, 0.0, 0.0))
+    }
+
+    #[test]
+    fn framing_a_far_target_pulls_the_camera_back() {
+        let near = framed_distance(1.0);
+        let far = framed_distance(30.0);
+        assert!((near - CAMERA_DISTANCE).abs() < 1e-3, "{near}");
+        let per_spread = CameraFollowSettings::default().frame_distance_per_spread;
+        assert!((far - 30.0 * per_spread).abs() < 1e-3, "{far}");
+        assert!(far > near);
+    }
+
+    #[test]
+    fn listener_mode_puts_the_ears_on_the_player_or_camera() {
+        for (mode, on_player) in [(ListenerMode::Player, true), (ListenerMode::Camera, false)] {
+            let mut app = set_up_player(|world| world.insert_resource(mode));
+            let world = app.world_mut();
+            let player = world.query_filtered::<Entity, With<Player>>().single(world);
+            let camera = world.query_filtered::<Entity, With<FollowPlayerCamera>>().single(world);
+            assert_eq!(world.get::<SpatialListener>(player).is_some(), on_player);
+            assert_eq!(world.get::<SpatialListener>(camera).is_some(), !on_player);
+        }
+    }
+
+    // player material after a second of fading, optionally with a wall between it and the camera
+    fn faded_player_material(occluder: bool) -> StandardMaterial {
+        let mut app = physics_app();
+        app.init_asset::<StandardMaterial>();
+        app.init_resource::<OcclusionFade>();
+        if occluder {
+            spawn_block(&mut app, Vec3::new(0.0, 1.5, 5.0), Vec3::new(2.0, 2.0, 0.5));
+        }
+        let player = spawn_player(&mut app, Vec3::new(0.0, 1.0, 0.0));
+        let material = app.world_mut().resource_mut::<Assets<StandardMaterial>>().add(StandardMaterial::default());
+        app.world_mut().entity_mut(player).insert(material.clone());
+        app.world_mut().spawn((
+            FollowPlayerCamera::default(),
+            TransformBundle::from_transform(Transform::from_xyz(0.0, 2.0, 10.0)),
+        ));
+        settle(&mut app);
+
+        for _ in 0..64 {
+            app.world_mut().run_system_once(fade_occluded_player);
+        }
+        app.world().resource::<Assets<StandardMaterial>>().get(&material).unwrap().clone()
+    }
+
+    #[test]
+    fn occluded_player_fades_out() {
+        let faded = faded_player_material(true);
+        assert_eq!(faded.base_color.alpha(), OcclusionFade::default().occluded_alpha);
+        assert!(matches!(faded.alpha_mode, AlphaMode::Blend));
+
+        let clear = faded_player_material(false);
+        assert_eq!(clear.base_color.alpha(), 1.0);
+        assert!(matches!(clear.alpha_mode, AlphaMode::Opaque));
+    }
+
+    #[test]
+    fn jitter_inside_the_deadzone_leaves_the_camera_still() {
+        let (mut app, player, camera) = camera_app(Vec3::ZERO);
+        app.world_mut().resource_mut::<CameraFollowSettings>().deadzone = Vec2::splat(1.0);
+        app.world_mut().run_system_once(follow_player_camera);
+        let start = camera_pos(&app, camera);
+
+        for offset in [Vec3::new(0.5, 0.0, 0.0), Vec3::new(-0.8, 0.0, 0.3), Vec3::new(0.2, 0.0, -0.9), Vec3::ZERO] {
+            move_player(&mut app, player, offset);
+            app.world_mut().run_system_once(follow_player_camera);
+            assert_eq!(camera_pos(&app, camera), start);
+        }
+
+        // leaving the box drags the camera by the overshoot
+        move_player(&mut app, player, Vec3::new(1.5, 0.0, 0.0));
+        app.world_mut().run_system_once(follow_player_camera);
+        let moved = camera_pos(&app, camera) - start;
+        assert!(moved.distance(Vec3::new(0.5, 0.0, 0.0)) < 1e-4, "{moved}");
+    }
+
+    // body center after grounding with the feet `gap` above a floor topped at y = 0
+    // (negative = sunk in)
+    fn grounded_y(ground_snap_enabled: bool, gap: f32) -> f32 {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.world_mut().resource_mut::<MovementState>().ground_snap_enabled = ground_snap_enabled;
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        let player = spawn_player(&mut app, Vec3::new(0.0, PLAYER_HALF_HEIGHT + gap, 0.0));
+        settle(&mut app);
+
+        app.world_mut().run_system_once(update_grounded_flag_and_snap);
+        assert!(!app.world().resource::<MovementState>().is_falling);
+        player_y(&app, player)
+    }
+
+    #[test]
+    fn without_ground_snap_only_penetration_is_corrected() {
+        let hover = 0.005;
+        assert!((grounded_y(true, hover) - PLAYER_HALF_HEIGHT).abs() < 1e-4);
+        assert_eq!(grounded_y(false, hover), PLAYER_HALF_HEIGHT + hover);
+
+        assert!((grounded_y(false, -0.1) - PLAYER_HALF_HEIGHT).abs() < 1e-4);
+    }
+
+    #[test]
+    fn jump_and_land_reports_airtime_and_peak_height() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.init_resource::<CameraTrauma>();
+        app.init_resource::<InputFrame>();
+        app.add_event::<ScriptedMove>();
+        app.add_event::<AirtimeStats>();
+        app.add_event::<Landed>();
+        app.add_event::<CameraShake>();
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        let player = spawn_player(&mut app, Vec3::new(0.0, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (movement_system, apply_player_motion, update_grounded_flag_and_snap, track_airtime).chain()
+        );
+        schedule.run(app.world_mut());
+
+        app.world_mut().insert_resource(InputFrame { jump: true, ..default() });
+        let mut airborne_ticks = 0;
+        let mut top = 0.0_f32;
+        let stats = loop {
+            schedule.run(app.world_mut());
+            app.world_mut().insert_resource(InputFrame::default());
+            let stats: Vec<AirtimeStats> = app.world_mut().resource_mut::<Events<AirtimeStats>>().drain().collect();
+            if let [stats] = stats[..] {
+                break stats;
+            }
+            airborne_ticks += 1;
+            top = top.max(player_y(&app, player) - PLAYER_HALF_HEIGHT);
+            assert!(airborne_ticks < 128, "never landed");
+        };
+
+        // v^2 / 2g and 2v / g for the default 10 up, 30 down
+        assert!((stats.peak_height - 10.0 / 6.0).abs() < 0.2, "{stats:?}");
+        assert!(stats.peak_height <= top);
+        assert!((stats.airtime - airborne_ticks as f32 * DT).abs() < 1e-4, "{stats:?}");
+        assert!((stats.airtime - 2.0 / 3.0).abs() < 2.0 * DT, "{stats:?}");
+    }
+
+    // grounded standing on a floor in collision group 2, with the ground checks masked to `mask`
+    fn grounded_on_group_2(mask: CollisionGroups) -> bool {
+        let mut app = physics_app();
+        app.insert_resource(PlayerGroundMask(mask));
+        let floor = spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        app.world_mut().entity_mut(floor).insert(CollisionGroups::new(Group::GROUP_2, Group::ALL));
+        spawn_player(&mut app, Vec3::new(0.0, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+
+        app.world_mut().run_system_once(update_grounded_flag_and_snap);
+        !app.world().resource::<MovementState>().is_falling
+    }
+
+    #[test]
+    fn ground_outside_the_mask_is_fallen_through() {
+        assert!(grounded_on_group_2(CollisionGroups::default()));
+        assert!(!grounded_on_group_2(CollisionGroups::new(Group::ALL, Group::GROUP_1)));
+    }
+
+    // x after a second teetering 0.2 past the +X edge (x = 5) of a floor topped at y = 0
+    fn teetering_x(edge_assist: bool, velocity: Vec2) -> f32 {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(5.0, 0.5, 5.0));
+        let player = spawn_player(&mut app, Vec3::new(5.2, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+        let mut st = app.world_mut().resource_mut::<MovementState>();
+        st.edge_assist = edge_assist;
+        st.velocity = velocity;
+
+        for _ in 0..64 {
+            app.world_mut().run_system_once(assist_away_from_edge);
+        }
+        app.world().get::<Transform>(player).unwrap().translation.x
+    }
+
+    #[test]
+    fn edge_assist_nudges_a_teetering_player_back() {
+        let speed = MovementState::default().edge_assist_speed;
+        let x = teetering_x(true, Vec2::ZERO);
+        assert!(x < 5.2 && x >= 5.2 - speed - 1e-4, "{x}");
+        assert_eq!(teetering_x(false, Vec2::ZERO), 5.2);
+        // walking off on purpose
+        assert_eq!(teetering_x(true, Vec2::X), 5.2);
+    }
+
+    #[test]
+    fn open_dialog_freezes_the_player_but_not_the_camera() {
+        let (mut app, player, camera) = camera_app(Vec3::ZERO);
+        app.init_resource::<PlayerGroundMask>();
+        app.init_resource::<DialogState>();
+        app.init_resource::<InputFrame>();
+        app.add_event::<ScriptedMove>();
+        app.insert_resource(InputFrame { move_dir: Vec2::Y, ..default() });
+        // the plugin's gating: motion in FixedUpdate behind dialog_closed, the camera always
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            ((movement_system, apply_player_motion).chain().run_if(dialog_closed), follow_player_camera).chain()
+        );
+        schedule.run(app.world_mut());
+
+        app.world_mut().resource_mut::<DialogState>().open = true;
+        let player_at = app.world().get::<Transform>(player).unwrap().translation;
+        let camera_at = camera_pos(&app, camera);
+        // orbiting the camera while the dialog is up
+        app.world_mut().get_mut::<FollowPlayerCamera>(camera).unwrap().yaw += 0.5;
+        for _ in 0..8 {
+            schedule.run(app.world_mut());
+        }
+        assert_eq!(app.world().get::<Transform>(player).unwrap().translation, player_at);
+        assert!(camera_pos(&app, camera).distance(camera_at) > 0.1);
+
+        app.world_mut().resource_mut::<DialogState>().open = false;
+        schedule.run(app.world_mut());
+        assert!(app.world().get::<Transform>(player).unwrap().translation.z > player_at.z);
+    }
+
+    #[test]
+    fn disabled_skybox_is_never_requested() {
+        for enabled in [true, false] {
+            let mut app = set_up_player(|world| world.insert_resource(SkyboxSettings { enabled }));
+            let world = app.world_mut();
+            let camera = world.query_filtered::<Entity, With<FollowPlayerCamera>>().single(world);
+            assert_eq!(world.get::<SkyboxHandle>(camera).is_some(), enabled);
+            assert!(world.get::<bevy::core_pipeline::Skybox>(camera).is_none());
+        }
+    }
+
+    #[test]
+    fn roll_impulse_swings_past_level_and_settles() {
+        let mut world = World::new();
+        world.insert_resource(fixed_time());
+        world.init_resource::<CameraRoll>();
+        world.resource_mut::<CameraRoll>().impulse(1.0);
+
+        let mut angles = Vec::new();
+        for _ in 0..256 {
+            world.run_system_once(stabilize_camera_roll);
+            angles.push(world.resource::<CameraRoll>().angle);
+        }
+
+        let high = angles.iter().copied().fold(0.0, f32::max);
+        let low = angles.iter().copied().fold(0.0, f32::min);
+        assert!(high > 0.0 && low < 0.0, "{angles:?}");
+        // the overshoot is smaller than the first swing
+        assert!(-low < high);
+        let roll = world.resource::<CameraRoll>();
+        assert_eq!((roll.angle, roll.velocity), (0.0, 0.0));
+    }
+
+    #[derive(Component)]
+    struct Ice {
+        grip: f32,
+    }
+
+    #[test]
+    fn registered_ground_effect_runs_only_on_its_marker() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.init_resource::<GroundEffects>();
+        app.world_mut().resource_mut::<GroundEffects>().register::<Ice>(|ground, st| {
+            st.decel_a = ground.get::<Ice>().unwrap().grip;
+        });
+        // ice on the left, plain floor on the right, both topped at y = 0
+        let ice = spawn_block(&mut app, Vec3::new(-3.0, -0.5, 0.0), Vec3::new(2.0, 0.5, 2.0));
+        app.world_mut().entity_mut(ice).insert(Ice { grip: 0.5 });
+        let plain = spawn_block(&mut app, Vec3::new(3.0, -0.5, 0.0), Vec3::new(2.0, 0.5, 2.0));
+        let player = spawn_player(&mut app, Vec3::new(3.0, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((update_grounded_flag_and_snap, apply_ground_effects).chain());
+        schedule.run(app.world_mut());
+        let st = app.world().resource::<MovementState>();
+        assert_eq!(st.standing_on, Some(plain));
+        assert_eq!(st.decel_a, MovementState::default().decel_a);
+
+        move_player(&mut app, player, Vec3::new(-3.0, PLAYER_HALF_HEIGHT, 0.0));
+        schedule.run(app.world_mut());
+        let st = app.world().resource::<MovementState>();
+        assert_eq!(st.standing_on, Some(ice));
+        assert_eq!(st.decel_a, 0.5);
+    }
+
+    #[test]
+    fn running_onto_a_boost_pad_launches_along_it() {
+        let mut app = physics_app();
+        app.init_resource::<PlayerGroundMask>();
+        app.init_resource::<GroundEffects>();
+        app.world_mut().resource_mut::<GroundEffects>().register::<BoostPad>(boost_pad_effect);
+        // floor, then a pad pointing +X further along +Z, both topped at y = 0
+        spawn_block(&mut app, Vec3::new(0.0, -0.5, 0.0), Vec3::new(2.0, 0.5, 2.0));
+        let pad = spawn_block(&mut app, Vec3::new(0.0, -0.5, 4.0), Vec3::new(2.0, 0.5, 2.0));
+        app.world_mut().entity_mut(pad).insert(BoostPad { direction: Vec2::X, speed: 20.0 });
+        let player = spawn_player(&mut app, Vec3::new(0.0, PLAYER_HALF_HEIGHT, 0.0));
+        settle(&mut app);
+        let mut st = app.world_mut().resource_mut::<MovementState>();
+        st.dir = Vec2::Y;
+        st.speed = 6.0;
+        st.velocity = Vec2::Y * 6.0;
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((update_grounded_flag_and_snap, apply_ground_effects).chain());
+        schedule.run(app.world_mut());
+        let before = app.world().resource::<MovementState>().snapshot();
+        assert_eq!(before.speed, 6.0);
+
+        move_player(&mut app, player, Vec3::new(0.0, PLAYER_HALF_HEIGHT, 4.0));
+        schedule.run(app.world_mut());
+
+        let st = app.world().resource::<MovementState>();
+        assert_eq!(st.standing_on, Some(pad));
+        assert_eq!(st.velocity, Vec2::X * 20.0);
+        let changed = st.diff(&before);
+        for vertical in ["fall_vel_y", "is_falling", "is_jumping", "ground_normal"] {
+            assert!(!changed.contains(&vertical), "{changed:?}");
+        }
+    }
 }
@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use bevy::{ input::gamepad::{ GamepadRumbleIntensity, GamepadRumbleRequest }, prelude::* };
+
+use crate::systems::{ movement_system, track_airtime, Landed, MovementState };
+
+pub struct RumblePlugin;
+
+impl Plugin for RumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RumbleSettings>();
+
+        app.add_systems(FixedUpdate, (
+            rumble_on_landing.after(track_airtime),
+            rumble_on_dash.after(movement_system),
+        ).run_if(rumble_enabled));
+    }
+}
+
+/// Gamepad rumble for landings (strong motor, scaled by impact speed) and dashes
+/// (weak motor, fixed pulse). Intensities are 0..1.
+#[derive(Resource)]
+pub struct RumbleSettings {
+    pub enabled: bool,
+
+    // landings below min_impact don't rumble; full_impact and up rumble at landing_intensity
+    pub landing_intensity: f32,
+    pub landing_min_impact: f32,
+    pub landing_full_impact: f32,
+    pub landing_duration: f32,
+
+    pub dash_intensity: f32,
+    pub dash_duration: f32,
+}
+
+impl Default for RumbleSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+
+            landing_intensity: 1.0,
+            landing_min_impact: 4.0,
+            landing_full_impact: 18.0, // terminal fall speed with default tuning
+            landing_duration: 0.2,
+
+            dash_intensity: 0.4,
+            dash_duration: 0.12,
+        }
+    }
+}
+
+impl RumbleSettings {
+    /// Strong-motor intensity for a landing at `impact_speed` (units/s, downward).
+    pub fn landing_strength(&self, impact_speed: f32) -> f32 {
+        let span = (self.landing_full_impact - self.landing_min_impact).max(0.001);
+        let t = ((impact_speed - self.landing_min_impact) / span).clamp(0.0, 1.0);
+        t * self.landing_intensity.clamp(0.0, 1.0)
+    }
+}
+
+fn rumble_enabled(settings: Res<RumbleSettings>) -> bool {
+    settings.enabled
+}
+
+fn rumble_all(
+    requests: &mut EventWriter<GamepadRumbleRequest>,
+    gamepads: &Gamepads,
+    intensity: GamepadRumbleIntensity,
+    seconds: f32
+) {
+    for gamepad in gamepads.iter() {
+        requests.send(GamepadRumbleRequest::Add {
+            gamepad,
+            intensity,
+            duration: Duration::from_secs_f32(seconds.max(0.0)),
+        });
+    }
+}
+
+fn rumble_on_landing(
+    settings: Res<RumbleSettings>,
+    gamepads: Res<Gamepads>,
+    mut landings: EventReader<Landed>,
+    mut requests: EventWriter<GamepadRumbleRequest>
+) {
+    for landing in landings.read() {
+        let strength = settings.landing_strength(landing.impact_speed);
+        if strength > 0.0 {
+            let intensity = GamepadRumbleIntensity::strong_motor(strength);
+            rumble_all(&mut requests, &gamepads, intensity, settings.landing_duration);
+        }
+    }
+}
+
+fn rumble_on_dash(
+    mut was_dashing: Local<bool>,
+    settings: Res<RumbleSettings>,
+    gamepads: Res<Gamepads>,
+    st: Res<MovementState>,
+    mut requests: EventWriter<GamepadRumbleRequest>
+) {
+    let dashing = st.dash_timer > 0.0;
+    if dashing && !*was_dashing {
+        let intensity = GamepadRumbleIntensity::weak_motor(settings.dash_intensity.clamp(0.0, 1.0));
+        rumble_all(&mut requests, &gamepads, intensity, settings.dash_duration);
+    }
+    *was_dashing = dashing;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::{
+        ecs::system::RunSystemOnce,
+        input::{ gamepad::{ GamepadConnection, GamepadConnectionEvent, GamepadInfo }, InputPlugin },
+    };
+
+    fn strong_rumbles_after_landing(impact_speed: f32) -> Vec<f32> {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+        app.init_resource::<RumbleSettings>();
+        app.add_event::<Landed>();
+        let pad = Gamepad::new(0);
+        let info = GamepadInfo { name: "pad".to_string() };
+        app.world_mut().send_event(GamepadConnectionEvent::new(pad, GamepadConnection::Connected(info)));
+        app.update();
+
+        app.world_mut().send_event(Landed { impact_speed, position: Vec3::ZERO });
+        app.world_mut().run_system_once(rumble_on_landing);
+
+        let mut requests = app.world_mut().resource_mut::<Events<GamepadRumbleRequest>>();
+        requests
+            .drain()
+            .map(|request| match request {
+                GamepadRumbleRequest::Add { gamepad, intensity, .. } => {
+                    assert_eq!(gamepad, pad);
+                    assert_eq!(intensity.weak_motor, 0.0);
+                    intensity.strong_motor
+                }
+                GamepadRumbleRequest::Stop { .. } => panic!("unexpected stop"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn landing_rumble_scales_with_impact() {
+        let settings = RumbleSettings::default();
+        assert_eq!(strong_rumbles_after_landing(settings.landing_full_impact), [settings.landing_intensity]);
+        let halfway = (settings.landing_min_impact + settings.landing_full_impact) / 2.0;
+        let half = strong_rumbles_after_landing(halfway);
+        assert!((half[0] - settings.landing_intensity / 2.0).abs() < 1e-5, "{half:?}");
+        assert!(strong_rumbles_after_landing(settings.landing_min_impact / 2.0).is_empty());
+    }
+}
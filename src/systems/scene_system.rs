@@ -2,53 +2,248 @@ use bevy::{
     asset::LoadState,
     core_pipeline::Skybox,
     prelude::*,
-    render::render_resource::{TextureViewDescriptor, TextureViewDimension},
+    render::{
+        camera::Exposure,
+        render_resource::{TextureViewDescriptor, TextureViewDimension},
+    },
 };
 use bevy_rapier3d::prelude::*;
 
+use crate::systems::dialog_closed;
+
 pub struct ScenePlugin;
 
 impl Plugin for ScenePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, (setup_light, setup_ground));
-        app.add_systems(Update, attach_skybox);
+        app.init_resource::<ReferenceScale>();
+        app.init_resource::<SkyboxSettings>();
+        app.init_resource::<SunGlare>();
+        app.add_systems(Startup, (setup_light, setup_ground, setup_reference_scale));
+        app.add_systems(Update, (attach_skybox.run_if(skybox_enabled), update_sun_glare));
+        // platforms pause with the player while a dialog is open
+        app.add_systems(FixedUpdate, move_platforms.run_if(dialog_closed));
     }
 }
 
 #[derive(Component)]
 pub struct SkyboxHandle(pub Handle<Image>);
 
+#[derive(Resource)]
+pub struct SkyboxSettings {
+    pub enabled: bool,
+}
+
+impl Default for SkyboxSettings {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+fn skybox_enabled(settings: Res<SkyboxSettings>) -> bool {
+    settings.enabled
+}
+
+/// How directly the camera looks into the directional light (1 = straight at it,
+/// -1 = sun behind), for glare post effects. Past `threshold` the camera stops
+/// down by up to `exposure_shift` EV (0 = only report the alignment).
+#[derive(Resource)]
+pub struct SunGlare {
+    pub alignment: f32,
+    pub threshold: f32,
+    pub exposure_shift: f32,
+}
+
+impl Default for SunGlare {
+    fn default() -> Self {
+        Self {
+            alignment: -1.0,
+            threshold: 0.9,
+            exposure_shift: 0.0,
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Ground;
 
+/// Blocks the player's horizontal movement (they slide along it).
+#[derive(Component)]
+pub struct Wall;
+
+/// Ground that launches the player along `direction` (movement space xz) at `speed`.
+#[derive(Component, Clone, Copy)]
+pub struct BoostPad {
+    pub direction: Vec2,
+    pub speed: f32,
+}
+
+/// Ground that travels back and forth between `a` and `b` at `speed` (units/s).
+/// The player standing on it is carried by `delta`, its motion this tick.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct MovingPlatform {
+    pub a: Vec3,
+    pub b: Vec3,
+    pub speed: f32,
+    pub delta: Vec3,
+    // 0 at a, 1 at b
+    progress: f32,
+    toward_b: bool,
+}
+
+impl MovingPlatform {
+    pub fn new(a: Vec3, b: Vec3, speed: f32) -> Self {
+        Self {
+            a,
+            b,
+            speed,
+            delta: Vec3::ZERO,
+            progress: 0.0,
+            toward_b: true,
+        }
+    }
+}
+
+/// Optional 1x1x1 cube for eyeballing level scale against the player.
+#[derive(Resource)]
+pub struct ReferenceScale {
+    pub enabled: bool,
+    // center of the cube (default: resting on the ground next to the origin)
+    pub position: Vec3,
+}
+
+impl Default for ReferenceScale {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            position: Vec3::new(2.0, 0.5, 0.0),
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ReferenceCube;
+
+pub const REFERENCE_CUBE_SIZE: f32 = 1.0;
+
+/// Visual + physics ground box centered at `position`; returns the entity so callers
+/// can add tags (BoostPad, ...).
+pub fn spawn_ground_block(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    position: Vec3,
+    size: Vec3,
+    color: Color,
+) -> Entity {
+    commands
+        .spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(Cuboid::new(size.x, size.y, size.z))),
+                material: materials.add(color),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            Ground,
+            RigidBody::Fixed,
+            Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+        ))
+        .id()
+}
+
+/// Fixed box the player can't walk through; not Ground, so it isn't stood on.
+pub fn spawn_wall(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &mut Assets<StandardMaterial>,
+    position: Vec3,
+    size: Vec3,
+) -> Entity {
+    commands
+        .spawn((
+            PbrBundle {
+                mesh: meshes.add(Mesh::from(Cuboid::new(size.x, size.y, size.z))),
+                material: materials.add(Color::srgb(0.55, 0.5, 0.45)),
+                transform: Transform::from_translation(position),
+                ..default()
+            },
+            Wall,
+            RigidBody::Fixed,
+            Collider::cuboid(size.x / 2.0, size.y / 2.0, size.z / 2.0),
+        ))
+        .id()
+}
+
+// Ground and walls come from the level file (LevelPlugin); this stands on its top at y=0.
 fn setup_ground(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
 ) {
-    let ground_size = 10.0;
-    let ground_height = 1.0;
+    // platform shuttling off the east edge, flush with the floor
+    let a = Vec3::new(6.5, -0.25, 0.0);
+    let platform = spawn_ground_block(
+        &mut commands,
+        &mut meshes,
+        &mut materials,
+        a,
+        Vec3::new(3.0, 0.5, 3.0),
+        Color::srgb(0.45, 0.45, 0.6),
+    );
+    commands
+        .entity(platform)
+        .insert((RigidBody::KinematicPositionBased, MovingPlatform::new(a, a + Vec3::X * 6.0, 2.0)));
+}
+
+pub fn move_platforms(time: Res<Time>, mut q: Query<(&mut MovingPlatform, &mut Transform)>) {
+    for (mut platform, mut t) in &mut q {
+        let length = platform.a.distance(platform.b);
+        if length <= 0.0 {
+            platform.delta = Vec3::ZERO;
+            continue;
+        }
+
+        // ping-pong along the segment
+        let step = platform.speed * time.delta_seconds() / length;
+        let mut progress = platform.progress + if platform.toward_b { step } else { -step };
+        if !(0.0..=1.0).contains(&progress) {
+            platform.toward_b = !platform.toward_b;
+            progress = progress.clamp(0.0, 1.0);
+        }
+        platform.progress = progress;
+
+        let position = platform.a.lerp(platform.b, progress);
+        platform.delta = position - t.translation;
+        t.translation = position;
+    }
+}
+
+fn setup_reference_scale(
+    mut commands: Commands,
+    reference: Res<ReferenceScale>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if !reference.enabled {
+        return;
+    }
 
-    // Visual + physics ground
+    // visual only, no collider
     commands.spawn((
         PbrBundle {
             mesh: meshes.add(Mesh::from(Cuboid::new(
-                ground_size,
-                ground_height,
-                ground_size,
+                REFERENCE_CUBE_SIZE,
+                REFERENCE_CUBE_SIZE,
+                REFERENCE_CUBE_SIZE,
             ))),
-            material: materials.add(Color::srgb(0.3, 0.5, 0.3)),
-            // top surface at y=0
-            transform: Transform::from_xyz(0.0, -ground_height / 2.0, 0.0),
+            material: materials.add(Color::srgb(1.0, 0.5, 0.1)),
+            transform: Transform::from_translation(reference.position),
             ..default()
         },
-        Ground,
-        RigidBody::Fixed,
-        Collider::cuboid(
-            ground_size / 2.0,
-            ground_height / 2.0,
-            ground_size / 2.0,
-        ),
+        ReferenceCube,
+        Name::new(format!(
+            "Reference {0} x {0} x {0} m",
+            REFERENCE_CUBE_SIZE
+        )),
     ));
 }
 
@@ -77,6 +272,30 @@ fn attach_skybox(
     }
 }
 
+fn update_sun_glare(
+    mut base_ev100: Local<Option<f32>>,
+    mut glare: ResMut<SunGlare>,
+    lights: Query<&GlobalTransform, With<DirectionalLight>>,
+    mut cams: Query<(&GlobalTransform, &mut Exposure), With<Camera3d>>
+) {
+    let (Ok(light), Ok((cam, mut exposure))) = (lights.get_single(), cams.get_single_mut()) else {
+        return;
+    };
+
+    // light travels along its forward; looking into it means facing the other way
+    let alignment = cam.forward().dot(-*light.forward());
+    if glare.alignment != alignment {
+        glare.alignment = alignment;
+    }
+
+    let base = *base_ev100.get_or_insert(exposure.ev100);
+    let over = ((alignment - glare.threshold) / (1.0 - glare.threshold).max(0.001)).clamp(0.0, 1.0);
+    let ev100 = base + glare.exposure_shift * over;
+    if exposure.ev100 != ev100 {
+        exposure.ev100 = ev100;
+    }
+}
+
 fn setup_light(mut commands: Commands) {
     commands.spawn(DirectionalLightBundle {
         directional_light: DirectionalLight {
@@ -92,4 +311,85 @@ fn setup_light(mut commands: Commands) {
         color: Color::srgb(0.4, 0.6, 1.0),
         brightness: 0.25,
     });
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    // camera looking along `look` with a sun shining along -Z
+    fn glare_world(look: Vec3) -> (World, Entity) {
+        let mut world = World::new();
+        world.insert_resource(SunGlare { exposure_shift: 2.0, ..default() });
+        world.spawn((
+            DirectionalLight::default(),
+            GlobalTransform::from(Transform::default().looking_to(Vec3::NEG_Z, Vec3::Y)),
+        ));
+        let camera = world
+            .spawn((
+                Camera3d::default(),
+                Exposure::default(),
+                GlobalTransform::from(Transform::default().looking_to(look, Vec3::Y)),
+            ))
+            .id();
+        (world, camera)
+    }
+
+    fn look(world: &mut World, camera: Entity, dir: Vec3) {
+        *world.get_mut::<GlobalTransform>(camera).unwrap() =
+            Transform::default().looking_to(dir, Vec3::Y).into();
+    }
+
+    #[test]
+    fn looking_into_the_sun_stops_the_camera_down() {
+        let (mut world, camera) = glare_world(Vec3::NEG_Z);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_sun_glare);
+        let base = Exposure::default().ev100;
+
+        // sun behind
+        schedule.run(&mut world);
+        assert!((world.resource::<SunGlare>().alignment + 1.0).abs() < 1e-5);
+        assert_eq!(world.get::<Exposure>(camera).unwrap().ev100, base);
+
+        // just short of the threshold
+        look(&mut world, camera, Vec3::new(0.5, 0.0, 1.0));
+        schedule.run(&mut world);
+        let glare = world.resource::<SunGlare>();
+        assert!(glare.alignment > 0.0 && glare.alignment < glare.threshold);
+        assert_eq!(world.get::<Exposure>(camera).unwrap().ev100, base);
+
+        // straight into it
+        look(&mut world, camera, Vec3::Z);
+        schedule.run(&mut world);
+        assert!((world.resource::<SunGlare>().alignment - 1.0).abs() < 1e-5);
+        let ev100 = world.get::<Exposure>(camera).unwrap().ev100;
+        assert!((ev100 - (base + 2.0)).abs() < 1e-4, "{ev100}");
+    }
+
+    fn reference_cubes(enabled: bool) -> Vec<(Transform, Vec3)> {
+        let mut world = World::new();
+        world.insert_resource(ReferenceScale { enabled, position: Vec3::new(-3.0, 0.5, 4.0) });
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<StandardMaterial>>();
+        world.run_system_once(setup_reference_scale);
+
+        let mut q = world.query_filtered::<(&Transform, &Handle<Mesh>), With<ReferenceCube>>();
+        q.iter(&world)
+            .map(|(t, mesh)| {
+                let aabb = world.resource::<Assets<Mesh>>().get(mesh).unwrap().compute_aabb().unwrap();
+                (*t, Vec3::from(aabb.half_extents) * 2.0)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reference_cube_spawns_only_when_enabled() {
+        assert!(reference_cubes(false).is_empty());
+
+        let cubes = reference_cubes(true);
+        assert_eq!(cubes.len(), 1);
+        assert_eq!(cubes[0].0.translation, Vec3::new(-3.0, 0.5, 4.0));
+        assert_eq!(cubes[0].1, Vec3::splat(REFERENCE_CUBE_SIZE));
+    }
+}
@@ -0,0 +1,209 @@
+use bevy::prelude::*;
+
+use crate::systems::{ sample_speed_curves, DebugSettings, MovementState };
+
+/// Side-by-side bar graphs of the accel and decel speed curves for the current tuning.
+#[derive(Resource)]
+pub struct CurveGraphSettings {
+    pub samples: usize,
+    // seconds covered by each graph
+    pub time_range: f32,
+}
+
+impl Default for CurveGraphSettings {
+    fn default() -> Self {
+        Self {
+            samples: 48,
+            time_range: 1.5,
+        }
+    }
+}
+
+pub struct CurveGraphUiPlugin;
+
+impl Plugin for CurveGraphUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CurveGraphSettings>();
+        app.add_systems(Startup, setup_curve_graph);
+        app.add_systems(Update, (curve_graph_visibility, curve_graph_render));
+    }
+}
+
+/// (accel, decel) speed at evenly spaced times over `time_range`.
+pub fn sample_curve_points(st: &MovementState, settings: &CurveGraphSettings) -> Vec<(f32, f32)> {
+    let n = settings.samples.max(1);
+    (0..n)
+        .map(|i| {
+            let t = (i as f32 / (n - 1).max(1) as f32) * settings.time_range;
+            sample_speed_curves(st, t)
+        })
+        .collect()
+}
+
+// ===== internal =====
+
+const GRAPH_HEIGHT_PX: f32 = 60.0;
+const BAR_WIDTH_PX: f32 = 2.0;
+
+#[derive(Component)]
+struct CurveGraph {
+    accel_bars: Vec<Entity>,
+    decel_bars: Vec<Entity>,
+    points: Vec<(f32, f32)>,
+}
+
+fn graph_panel() -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            height: Val::Px(GRAPH_HEIGHT_PX),
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::FlexEnd,
+            column_gap: Val::Px(1.0),
+            padding: UiRect::all(Val::Px(4.0)),
+            ..default()
+        },
+        background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        ..default()
+    }
+}
+
+fn spawn_bars(p: &mut ChildBuilder, count: usize, color: Color) -> Vec<Entity> {
+    (0..count)
+        .map(|_| {
+            p.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(BAR_WIDTH_PX),
+                    height: Val::Px(1.0),
+                    ..default()
+                },
+                background_color: BackgroundColor(color),
+                ..default()
+            }).id()
+        })
+        .collect()
+}
+
+fn setup_curve_graph(mut commands: Commands, settings: Res<CurveGraphSettings>) {
+    let count = settings.samples.max(1);
+    let mut accel_bars = Vec::new();
+    let mut decel_bars = Vec::new();
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(6.0),
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            ..default()
+        })
+        .with_children(|root| {
+            root.spawn(graph_panel()).with_children(|p| {
+                accel_bars = spawn_bars(p, count, Color::srgb(0.2, 1.0, 0.2));
+            });
+            root.spawn(graph_panel()).with_children(|p| {
+                decel_bars = spawn_bars(p, count, Color::srgb(1.0, 0.4, 0.2));
+            });
+        })
+        .insert(CurveGraph {
+            accel_bars,
+            decel_bars,
+            points: Vec::new(),
+        });
+}
+
+// developer overlay: only shown with the debug toggle on
+fn curve_graph_visibility(debug: Res<DebugSettings>, mut q: Query<&mut Visibility, With<CurveGraph>>) {
+    if !debug.is_changed() {
+        return;
+    }
+
+    for mut vis in &mut q {
+        *vis = if debug.enabled { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
+
+fn curve_graph_render(
+    st: Res<MovementState>,
+    settings: Res<CurveGraphSettings>,
+    mut graphs: Query<&mut CurveGraph>,
+    mut styles: Query<&mut Style>
+) {
+    for mut graph in &mut graphs {
+        let points = sample_curve_points(&st, &settings);
+
+        // tuning rarely changes; skip restyling when the curves are identical
+        if points == graph.points {
+            continue;
+        }
+
+        let h = GRAPH_HEIGHT_PX - 8.0;
+        let max = st.max_speed.max(0.001);
+
+        for (i, &(accel, decel)) in points.iter().enumerate() {
+            let bars = [(graph.accel_bars.get(i), accel), (graph.decel_bars.get(i), decel)];
+            for (bar, speed) in bars {
+                let Some(&bar) = bar else { continue; };
+                if let Ok(mut style) = styles.get_mut(bar) {
+                    style.height = Val::Px(1.0 + (speed / max).clamp(0.0, 1.0) * h);
+                }
+            }
+        }
+
+        graph.points = points;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    #[test]
+    fn points_follow_the_tuned_curves() {
+        let mut st = MovementState::default();
+        st.max_speed = 8.0;
+        st.accel_k = 3.0;
+        st.decel_a = 2.0;
+        let settings = CurveGraphSettings { samples: 4, time_range: 1.5 };
+
+        let points = sample_curve_points(&st, &settings);
+        assert_eq!(points.len(), 4);
+        for (i, &(accel, decel)) in points.iter().enumerate() {
+            let t = i as f32 * 0.5;
+            assert!((accel - 8.0 * (1.0 - (-3.0 * t).exp())).abs() < 1e-5, "{t}: {accel}");
+            assert!((decel - 8.0 / (1.0 + 2.0 * t).powi(2)).abs() < 1e-5, "{t}: {decel}");
+        }
+    }
+
+    #[test]
+    fn bars_are_as_tall_as_the_speeds() {
+        let mut world = World::new();
+        world.init_resource::<MovementState>();
+        world.init_resource::<CurveGraphSettings>();
+        world.run_system_once(setup_curve_graph);
+        world.run_system_once(curve_graph_render);
+
+        let height = |world: &World, bar| match world.get::<Style>(bar).unwrap().height {
+            Val::Px(px) => px,
+            other => panic!("{other:?}"),
+        };
+        let mut graphs = world.query::<&CurveGraph>();
+        let graph = graphs.single(&world);
+        let full = GRAPH_HEIGHT_PX - 8.0 + 1.0;
+
+        // from rest / from max_speed at t = 0
+        assert_eq!(height(&world, graph.accel_bars[0]), 1.0);
+        assert_eq!(height(&world, graph.decel_bars[0]), full);
+        for pair in graph.accel_bars.windows(2) {
+            assert!(height(&world, pair[1]) >= height(&world, pair[0]));
+        }
+        for pair in graph.decel_bars.windows(2) {
+            assert!(height(&world, pair[1]) <= height(&world, pair[0]));
+        }
+    }
+}
@@ -45,24 +45,87 @@ impl Default for HeartbeatBundle {
                 scale_max: 1.0,
                 scale_lerp: 0.12, // 0..1 (higher = scale adapts faster)
 
+                update_hz: 60.0, // samples/s, independent of frame rate
+                accum: 0.0,
+
+                // threshold flash (off until a threshold is set)
+                flash_threshold: None,
+                flash: 0.0,
+                flash_decay_per_s: 3.0, // full flash fades in ~0.33s
+                last_value: 0.0,
+                background: Color::NONE,
+
                 // visuals
                 bar_width_px: 2.0,
                 min_bar_px: 1.0,
+                baseline_px: 3.0,
             },
         }
     }
 }
 
+impl HeartbeatBundle {
+    /// Sets how many samples per second the widget records (frame-rate independent).
+    pub fn with_update_hz(mut self, hz: f32) -> Self {
+        self.hb.update_hz = hz.max(1.0);
+        self
+    }
+
+    /// Flashes the widget background whenever the value rises through `threshold`.
+    pub fn with_flash_threshold(mut self, threshold: f32) -> Self {
+        self.hb.flash_threshold = Some(threshold);
+        self
+    }
+}
+
+/// Caps the total number of bar entities across all heartbeat widgets.
+/// Widgets spawned over budget get fewer bars (each bar shows a bucket of samples).
+#[derive(Resource)]
+pub struct HeartbeatBudget {
+    pub max_bars: usize,
+}
+
+impl Default for HeartbeatBudget {
+    fn default() -> Self {
+        Self { max_bars: 1024 }
+    }
+}
+
+/// Pressing `key` writes each widget's sample history to `path` as CSV
+/// (oldest first); extra widgets get `_1`, `_2`, ... before the extension.
+#[derive(Resource)]
+pub struct HeartbeatExport {
+    pub key: KeyCode,
+    pub path: String,
+}
+
+impl Default for HeartbeatExport {
+    fn default() -> Self {
+        Self {
+            key: KeyCode::F6,
+            path: "heartbeat.csv".to_string(),
+        }
+    }
+}
+
 pub struct HeartbeatUiPlugin;
 
 impl Plugin for HeartbeatUiPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<HeartbeatBudget>();
+        app.init_resource::<HeartbeatExport>();
         app.add_systems(Update, (heartbeat_init_bars, heartbeat_tick, heartbeat_render).chain());
+        app.add_systems(Update, heartbeat_export_csv.after(heartbeat_tick));
     }
 }
 
 // ===== internal =====
 
+// cap catch-up after long hitches
+const MAX_STEPS_PER_FRAME: u32 = 8;
+
+const FLASH_COLOR: Color = Color::srgba(1.0, 0.3, 0.2, 0.85);
+
 #[derive(Component)]
 pub(crate) struct Heartbeat {
     max_samples: usize,
@@ -78,24 +141,64 @@ pub(crate) struct Heartbeat {
     scale_max: f32,
     scale_lerp: f32,
 
+    // fixed update rate, decoupled from frame rate
+    update_hz: f32,
+    accum: f32,
+
+    // background flash on an upward threshold crossing (0..1, decays)
+    flash_threshold: Option<f32>,
+    flash: f32,
+    flash_decay_per_s: f32,
+    last_value: f32,
+    background: Color, // root color captured at spawn, the flash blends from it
+
     // visuals
     bar_width_px: f32,
     min_bar_px: f32,
+    baseline_px: f32, // idle bar height; the scale maps above it
+}
+
+// `sample,value` CSV of `samples` (oldest first), as written by HeartbeatExport
+fn heartbeat_csv(samples: &[f32]) -> String {
+    let mut csv = String::from("sample,value\n");
+    for (i, s) in samples.iter().enumerate() {
+        csv.push_str(&format!("{i},{s}\n"));
+    }
+    csv
 }
 
 fn heartbeat_init_bars(
     mut commands: Commands,
-    mut q: Query<(Entity, &mut Heartbeat), Added<Heartbeat>>
+    budget: Res<HeartbeatBudget>,
+    mut q: Query<(Entity, &mut Heartbeat, &BackgroundColor)>
 ) {
-    for (entity, mut hb) in &mut q {
-        let max_samples = hb.max_samples;
+    // bars already owned by existing widgets count against the budget
+    let mut used = 0;
+    let mut added = Vec::new();
+    for (entity, hb, _) in &mut q {
+        if hb.is_added() {
+            added.push(entity);
+        } else {
+            used += hb.bars.len();
+        }
+    }
+
+    for (i, &entity) in added.iter().enumerate() {
+        let Ok((_, mut hb, bg)) = q.get_mut(entity) else { continue; };
+        hb.background = bg.0;
+
+        // split what's left evenly between the widgets still waiting for bars
+        let share = budget.max_bars.saturating_sub(used) / (added.len() - i);
+        let bar_count = hb.max_samples.min(share);
+        used += bar_count;
+
         let bar_width_px = hb.bar_width_px;
         let min_bar_px = hb.min_bar_px;
 
-        let mut bars = Vec::with_capacity(max_samples);
+        let mut bars = Vec::with_capacity(bar_count);
 
         commands.entity(entity).with_children(|p| {
-            for _ in 0..max_samples {
+            for _ in 0..bar_count {
                 let e = p
                     .spawn(NodeBundle {
                         style: Style {
@@ -116,59 +219,116 @@ fn heartbeat_init_bars(
 }
 
 fn heartbeat_tick(time: Res<Time>, mut q: Query<(&HeartbeatValue, &mut Heartbeat)>) {
-    let dt = time.delta_seconds();
-
     for (v, mut hb) in &mut q {
-        // EMA smoothing
-        if hb.samples.is_empty() {
-            hb.ema = v.0;
-        } else {
-            hb.ema = hb.ema + (v.0 - hb.ema) * hb.ema_alpha;
-        }
+        // fixed-rate steps so the EMA/history advance with wall time, not frame rate
+        let step = 1.0 / hb.update_hz.max(1.0);
+        hb.accum = (hb.accum + time.delta_seconds()).min(step * MAX_STEPS_PER_FRAME as f32);
 
-        // Peak hold
-        hb.peak = hb.peak.max(hb.ema);
-        hb.peak = (hb.peak - hb.peak_fall_per_s * dt).max(hb.ema);
+        while hb.accum >= step {
+            hb.accum -= step;
+            heartbeat_step(&mut hb, v.0, step);
+        }
+    }
+}
 
-        // Push sample into fixed window
-        if hb.samples.len() >= hb.max_samples {
-            hb.samples.remove(0);
+fn heartbeat_step(hb: &mut Heartbeat, value: f32, dt: f32) {
+    // threshold flash: retrigger on each upward crossing, fade otherwise
+    hb.flash = (hb.flash - hb.flash_decay_per_s * dt).max(0.0);
+    if let Some(th) = hb.flash_threshold {
+        if hb.last_value < th && value >= th {
+            hb.flash = 1.0;
         }
+    }
+    hb.last_value = value;
 
-        let ema = hb.ema; // <-- local avoids E0502
-        hb.samples.push(ema);
+    // EMA smoothing
+    if hb.samples.is_empty() {
+        hb.ema = value;
+    } else {
+        hb.ema = hb.ema + (value - hb.ema) * hb.ema_alpha;
+    }
 
-        // keep fixed window size (avoid growth if max_samples changes)
-        if hb.samples.len() < hb.max_samples {
-            let missing = hb.max_samples - hb.samples.len();
-            hb.samples.splice(0..0, std::iter::repeat(0.0).take(missing));
-        }
+    // Peak hold
+    hb.peak = hb.peak.max(hb.ema);
+    hb.peak = (hb.peak - hb.peak_fall_per_s * dt).max(hb.ema);
 
-        // Soft autoscale
-        let (mut wmin, mut wmax) = (f32::INFINITY, f32::NEG_INFINITY);
-        for &s in &hb.samples {
-            wmin = wmin.min(s);
-            wmax = wmax.max(s);
-        }
+    // Push sample into fixed window
+    if hb.samples.len() >= hb.max_samples {
+        hb.samples.remove(0);
+    }
+
+    let ema = hb.ema; // <-- local avoids E0502
+    hb.samples.push(ema);
+
+    // keep fixed window size (avoid growth if max_samples changes)
+    if hb.samples.len() < hb.max_samples {
+        let missing = hb.max_samples - hb.samples.len();
+        hb.samples.splice(0..0, std::iter::repeat_n(0.0, missing));
+    }
+
+    // Soft autoscale
+    let (mut wmin, mut wmax) = (f32::INFINITY, f32::NEG_INFINITY);
+    for &s in &hb.samples {
+        wmin = wmin.min(s);
+        wmax = wmax.max(s);
+    }
+
+    if wmin.is_finite() && wmax.is_finite() {
+        let pad = (wmax - wmin).max(0.001) * 0.08;
+        // don't pad below zero, so an idle (0) value sits on the baseline
+        wmin = (wmin - pad).max(wmin.min(0.0));
+        wmax += pad;
+
+        let lerp_t = hb.scale_lerp;
+        hb.scale_min = hb.scale_min + (wmin - hb.scale_min) * lerp_t;
+        hb.scale_max = hb.scale_max + (wmax - hb.scale_max) * lerp_t;
+    }
+}
 
-        if wmin.is_finite() && wmax.is_finite() {
-            let pad = (wmax - wmin).max(0.001) * 0.08;
-            wmin -= pad;
-            wmax += pad;
+fn heartbeat_export_csv(
+    keys: Res<ButtonInput<KeyCode>>,
+    export: Res<HeartbeatExport>,
+    q: Query<&Heartbeat>
+) {
+    if !keys.just_pressed(export.key) {
+        return;
+    }
 
-            let lerp_t = hb.scale_lerp;
-            hb.scale_min = hb.scale_min + (wmin - hb.scale_min) * lerp_t;
-            hb.scale_max = hb.scale_max + (wmax - hb.scale_max) * lerp_t;
+    for (i, hb) in q.iter().enumerate() {
+        let path = match (i, export.path.rsplit_once('.')) {
+            (0, _) => export.path.clone(),
+            (_, Some((stem, ext))) => format!("{stem}_{i}.{ext}"),
+            (_, None) => format!("{}_{i}", export.path),
+        };
+
+        // no filesystem on the web build; this just reports the error there
+        match std::fs::write(&path, heartbeat_csv(&hb.samples)) {
+            Ok(()) => info!("exported {} heartbeat samples to {path}", hb.samples.len()),
+            Err(err) => warn!("heartbeat export: couldn't write {path} ({err})"),
         }
     }
 }
 
+// bar height for sample `s` in `h` px: baseline_px at the bottom of the scale
+fn bar_height(hb: &Heartbeat, s: f32, h: f32) -> f32 {
+    let denom = (hb.scale_max - hb.scale_min).max(0.001);
+    let t = ((s - hb.scale_min) / denom).clamp(0.0, 1.0);
+    let base = hb.min_bar_px.max(hb.baseline_px).min(h);
+    base + t * (h - base)
+}
+
 fn heartbeat_render(
-    roots: Query<(&Heartbeat, &Node), With<HeartbeatValue>>,
+    roots: Query<(Entity, &Heartbeat, &Node), With<HeartbeatValue>>,
     mut styles: Query<&mut Style>,
     mut colors: Query<&mut BackgroundColor>
 ) {
-    for (hb, node) in &roots {
+    for (entity, hb, node) in &roots {
+        if hb.flash_threshold.is_some() {
+            if let Ok(mut bg) = colors.get_mut(entity) {
+                bg.0 = hb.background.mix(&FLASH_COLOR, hb.flash);
+            }
+        }
+
         if hb.bars.is_empty() {
             continue;
         }
@@ -176,21 +336,19 @@ fn heartbeat_render(
         // available height inside padding (8px from UiRect::all(4))
         let h = (node.size().y - 8.0).max(1.0);
 
-        let min = hb.scale_min;
-        let max = hb.scale_max;
-        let denom = (max - min).max(0.001);
-
         // peak threshold: top ~10% of the current scale
-        let peak_threshold = max - 0.1 * denom;
+        let peak_threshold = hb.scale_max - 0.1 * (hb.scale_max - hb.scale_min).max(0.001);
 
-        for (i, &s) in hb.samples.iter().enumerate().take(hb.bars.len()) {
-            let bar = hb.bars[i];
+        // over budget: each bar shows the max of its bucket of samples
+        let n = hb.bars.len();
+        let m = hb.samples.len();
 
-            let t = ((s - min) / denom).clamp(0.0, 1.0);
-            let bar_h = hb.min_bar_px + t * h;
+        for (i, &bar) in hb.bars.iter().enumerate() {
+            let bucket = &hb.samples[(i * m) / n..((i + 1) * m) / n];
+            let Some(s) = bucket.iter().copied().reduce(f32::max) else { continue; };
 
             if let Ok(mut st) = styles.get_mut(bar) {
-                st.height = Val::Px(bar_h);
+                st.height = Val::Px(bar_height(hb, s, h));
             }
 
             // Make peaks brighter / more opaque
@@ -203,4 +361,137 @@ fn heartbeat_render(
             }
         }
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use std::time::Duration;
+
+    // `value` fed for two seconds at the widget's update rate
+    fn fed(value: f32) -> Heartbeat {
+        let mut hb = HeartbeatBundle::default().hb;
+        let step = 1.0 / hb.update_hz;
+        for _ in 0..(2.0 * hb.update_hz) as usize {
+            heartbeat_step(&mut hb, value, step);
+        }
+        hb
+    }
+
+    #[test]
+    fn bars_across_widgets_stay_within_the_budget() {
+        let mut world = World::new();
+        world.insert_resource(HeartbeatBudget { max_bars: 300 });
+        let mut schedule = Schedule::default();
+        schedule.add_systems(heartbeat_init_bars);
+        let bar_count = |world: &mut World| world.query::<&Parent>().iter(world).count();
+
+        // 5 x 120 samples wanted
+        for _ in 0..5 {
+            world.spawn(HeartbeatBundle::default());
+        }
+        schedule.run(&mut world);
+        assert_eq!(bar_count(&mut world), 300);
+        let mut widgets = world.query::<&Heartbeat>();
+        assert!(widgets.iter(&world).all(|hb| hb.bars.len() == 60));
+
+        // budget used up: later widgets get no bars rather than going over
+        world.spawn(HeartbeatBundle::default());
+        schedule.run(&mut world);
+        assert_eq!(bar_count(&mut world), 300);
+    }
+
+    // one second of `value` through heartbeat_tick at `fps`
+    fn ticked_at(fps: u32, value: f32) -> Heartbeat {
+        let mut world = World::new();
+        world.init_resource::<Time>();
+        let widget = world.spawn(HeartbeatBundle { value: HeartbeatValue(value), ..default() }).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(heartbeat_tick);
+        for _ in 0..fps {
+            world.resource_mut::<Time>().advance_by(Duration::from_secs_f64(1.0 / fps as f64));
+            schedule.run(&mut world);
+        }
+        world.entity_mut(widget).take::<HeartbeatBundle>().unwrap().hb
+    }
+
+    #[test]
+    fn frame_rate_does_not_change_the_trajectory() {
+        let slow = ticked_at(30, 4.0);
+        let fast = ticked_at(240, 4.0);
+        assert!((slow.ema - fast.ema).abs() < 1e-3, "{} vs {}", slow.ema, fast.ema);
+        // ~60 samples either way (rounding in the accumulator can shift one)
+        let recorded = |hb: &Heartbeat| hb.samples.iter().filter(|&&s| s > 0.0).count() as i32;
+        assert!((recorded(&slow) - 60).abs() <= 1 && (recorded(&fast) - 60).abs() <= 1);
+        // still climbing toward 4 after a second at 60 Hz
+        assert!(slow.ema > 3.9 && slow.ema < 4.0);
+    }
+
+    #[test]
+    fn csv_lists_every_sample_in_order() {
+        assert_eq!(heartbeat_csv(&[0.0, 1.5, -2.25]), "sample,value\n0,0\n1,1.5\n2,-2.25\n");
+        assert_eq!(heartbeat_csv(&[]), "sample,value\n");
+    }
+
+    #[test]
+    fn export_key_writes_the_current_samples() {
+        let path = std::env::temp_dir().join(format!("heartbeat_export_{}.csv", std::process::id()));
+        let mut world = World::new();
+        world.insert_resource(HeartbeatExport { key: KeyCode::F6, path: path.display().to_string() });
+        world.init_resource::<ButtonInput<KeyCode>>();
+        let hb = fed(3.0);
+        let expected = heartbeat_csv(&hb.samples);
+        world.spawn(HeartbeatBundle { hb, ..default() });
+
+        // nothing until the key goes down
+        world.run_system_once(heartbeat_export_csv);
+        assert!(!path.exists());
+
+        world.resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::F6);
+        world.run_system_once(heartbeat_export_csv);
+        let written = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(written, expected);
+        assert_eq!(written.lines().count(), 1 + HeartbeatBundle::default().hb.max_samples);
+    }
+
+    #[test]
+    fn zero_samples_draw_at_the_baseline() {
+        let hb = fed(0.0);
+        for &s in &hb.samples {
+            assert_eq!(bar_height(&hb, s, 42.0), hb.baseline_px);
+        }
+
+        // anything above idle rises off it
+        let mut hb = fed(0.0);
+        let step = 1.0 / hb.update_hz;
+        heartbeat_step(&mut hb, 5.0, step);
+        let last = *hb.samples.last().unwrap();
+        assert!(bar_height(&hb, last, 42.0) > hb.baseline_px);
+    }
+
+    #[test]
+    fn crossing_the_flash_threshold_flashes_then_fades() {
+        let mut hb = HeartbeatBundle::default().with_flash_threshold(5.0).hb;
+        let step = 1.0 / hb.update_hz;
+
+        heartbeat_step(&mut hb, 4.0, step);
+        assert_eq!(hb.flash, 0.0);
+        heartbeat_step(&mut hb, 6.0, step);
+        assert_eq!(hb.flash, 1.0);
+
+        // staying above doesn't retrigger; it fades out within 1 / flash_decay_per_s
+        let mut last = hb.flash;
+        for _ in 0..(hb.update_hz / hb.flash_decay_per_s).ceil() as usize {
+            heartbeat_step(&mut hb, 6.0, step);
+            assert!(hb.flash < last || hb.flash == 0.0);
+            last = hb.flash;
+        }
+        assert_eq!(hb.flash, 0.0);
+
+        // dipping below and back up flashes again
+        heartbeat_step(&mut hb, 4.0, step);
+        heartbeat_step(&mut hb, 5.0, step);
+        assert_eq!(hb.flash, 1.0);
+    }
+}
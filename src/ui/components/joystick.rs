@@ -0,0 +1,170 @@
+use bevy::prelude::*;
+
+use crate::systems::{ MovementState, VirtualJoystick };
+
+pub struct JoystickUiPlugin;
+
+impl Plugin for JoystickUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_joystick);
+        app.add_systems(Update, (joystick_touch, press_jump_button));
+    }
+}
+
+// ===== internal =====
+
+const BASE_SIZE_PX: f32 = 120.0;
+const KNOB_SIZE_PX: f32 = 50.0;
+const JUMP_BUTTON_SIZE_PX: f32 = 80.0;
+
+#[derive(Component)]
+struct JumpButton;
+
+#[derive(Component, Default)]
+struct Joystick {
+    knob: Option<Entity>,
+    touch_id: Option<u64>,
+}
+
+fn knob_style(offset: Vec2) -> Style {
+    let rest = (BASE_SIZE_PX - KNOB_SIZE_PX) / 2.0;
+    Style {
+        position_type: PositionType::Absolute,
+        left: Val::Px(rest + offset.x),
+        top: Val::Px(rest + offset.y),
+        width: Val::Px(KNOB_SIZE_PX),
+        height: Val::Px(KNOB_SIZE_PX),
+        ..default()
+    }
+}
+
+fn setup_joystick(mut commands: Commands, stick: Res<VirtualJoystick>) {
+    if !stick.enabled {
+        return;
+    }
+
+    let mut knob = None;
+
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(30.0),
+                right: Val::Px(30.0),
+                width: Val::Px(BASE_SIZE_PX),
+                height: Val::Px(BASE_SIZE_PX),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.3)),
+            border_radius: BorderRadius::MAX,
+            ..default()
+        })
+        .with_children(|p| {
+            knob = Some(
+                p.spawn(NodeBundle {
+                    style: knob_style(Vec2::ZERO),
+                    background_color: BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.6)),
+                    border_radius: BorderRadius::MAX,
+                    ..default()
+                }).id()
+            );
+        })
+        .insert(Joystick { knob, touch_id: None });
+
+    // jump on the opposite side so both thumbs have a control
+    commands.spawn((
+        ButtonBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(50.0),
+                left: Val::Px(30.0),
+                width: Val::Px(JUMP_BUTTON_SIZE_PX),
+                height: Val::Px(JUMP_BUTTON_SIZE_PX),
+                ..default()
+            },
+            background_color: BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.4)),
+            border_radius: BorderRadius::MAX,
+            ..default()
+        },
+        JumpButton,
+    ));
+}
+
+// Same latch as the Jump key, so the press survives frames without a FixedUpdate tick.
+fn press_jump_button(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<JumpButton>)>,
+    mut st: ResMut<MovementState>
+) {
+    if buttons.iter().any(|i| *i == Interaction::Pressed) {
+        st.press_jump();
+    }
+}
+
+fn joystick_touch(
+    touches: Res<Touches>,
+    mut stick: ResMut<VirtualJoystick>,
+    mut q: Query<(&mut Joystick, &Node, &GlobalTransform)>,
+    mut styles: Query<&mut Style>
+) {
+    let Ok((mut js, node, gt)) = q.get_single_mut() else {
+        return;
+    };
+
+    let center = gt.translation().truncate();
+    let radius = node.size().x / 2.0;
+
+    // claim a touch that starts on the base
+    if js.touch_id.is_none() {
+        js.touch_id = touches
+            .iter_just_pressed()
+            .find(|t| t.position().distance(center) <= radius)
+            .map(|t| t.id());
+    }
+
+    let offset = match js.touch_id.and_then(|id| touches.get_pressed(id)) {
+        Some(t) => (t.position() - center).clamp_length_max(radius),
+        None => {
+            js.touch_id = None;
+            Vec2::ZERO
+        }
+    };
+
+    // screen y grows downward, stick y is forward
+    let vector = Vec2::new(offset.x, -offset.y) / radius.max(1.0);
+    if stick.vector == vector {
+        return;
+    }
+    stick.vector = vector;
+
+    if let Some(mut style) = js.knob.and_then(|k| styles.get_mut(k).ok()) {
+        *style = knob_style(offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+    use crate::systems::{ capture_input_frame, InputFrame, Keybindings };
+
+    #[test]
+    fn jump_button_press_reaches_the_next_input_frame() {
+        let mut world = World::new();
+        world.init_resource::<MovementState>();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Keybindings>();
+        world.init_resource::<VirtualJoystick>();
+        world.init_resource::<Gamepads>();
+        world.init_resource::<Axis<GamepadAxis>>();
+        world.init_resource::<InputFrame>();
+        world.spawn((JumpButton, Interaction::Pressed));
+
+        world.run_system_once(press_jump_button);
+        world.run_system_once(capture_input_frame);
+        assert!(world.resource::<InputFrame>().jump);
+
+        // the latch is consumed by that frame
+        world.run_system_once(capture_input_frame);
+        assert!(!world.resource::<InputFrame>().jump);
+    }
+}
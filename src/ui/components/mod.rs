@@ -1,3 +1,13 @@
 mod heartbeat;
+mod curve_graph;
+mod joystick;
+mod rebind;
+mod reticle;
+mod vignette;
 
 pub use heartbeat::{ HeartbeatBundle, HeartbeatUiPlugin, HeartbeatValue };
+pub use curve_graph::CurveGraphUiPlugin;
+pub use joystick::JoystickUiPlugin;
+pub use rebind::RebindUiPlugin;
+pub use reticle::ReticleUiPlugin;
+pub use vignette::VignetteUiPlugin;
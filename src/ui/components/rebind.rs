@@ -0,0 +1,225 @@
+use bevy::prelude::*;
+
+use crate::systems::{ InputAction, Keybindings };
+
+pub const REBIND_MENU_KEY: KeyCode = KeyCode::F7;
+
+/// Action waiting for its new key after its row was clicked; None when not capturing.
+#[derive(Resource, Default)]
+pub struct RebindCapture(pub Option<InputAction>);
+
+/// Key binding list (toggled with REBIND_MENU_KEY): click a row, then press the new key.
+pub struct RebindUiPlugin;
+
+impl Plugin for RebindUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RebindCapture>();
+        app.add_systems(Startup, setup_rebind_menu);
+        app.add_systems(Update, (
+            toggle_rebind_menu,
+            start_rebind_capture,
+            capture_rebind_key.after(toggle_rebind_menu).after(start_rebind_capture),
+            update_rebind_rows.after(capture_rebind_key),
+        ));
+    }
+}
+
+// ===== internal =====
+
+#[derive(Component)]
+struct RebindMenu;
+
+#[derive(Component)]
+struct RebindRow(InputAction);
+
+fn setup_rebind_menu(mut commands: Commands) {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(100.0),
+                    left: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(2.0),
+                    padding: UiRect::all(Val::Px(6.0)),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+                visibility: Visibility::Hidden,
+                ..default()
+            },
+            RebindMenu,
+        ))
+        .with_children(|menu| {
+            for action in InputAction::ALL {
+                menu.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)),
+                            ..default()
+                        },
+                        background_color: BackgroundColor(Color::NONE),
+                        ..default()
+                    },
+                    RebindRow(action),
+                ))
+                .with_children(|row| {
+                    row.spawn(TextBundle::from_section("", TextStyle {
+                        font_size: 16.0,
+                        color: Color::WHITE,
+                        ..default()
+                    }));
+                });
+            }
+        });
+}
+
+fn toggle_rebind_menu(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut capture: ResMut<RebindCapture>,
+    mut q: Query<&mut Visibility, With<RebindMenu>>
+) {
+    if !keys.just_pressed(REBIND_MENU_KEY) {
+        return;
+    }
+
+    for mut vis in &mut q {
+        *vis = if *vis == Visibility::Hidden { Visibility::Inherited } else { Visibility::Hidden };
+    }
+    capture.0 = None;
+}
+
+fn start_rebind_capture(
+    rows: Query<(&Interaction, &RebindRow), Changed<Interaction>>,
+    mut capture: ResMut<RebindCapture>
+) {
+    for (interaction, row) in &rows {
+        if *interaction == Interaction::Pressed {
+            capture.0 = Some(row.0);
+        }
+    }
+}
+
+// The next key pressed while capturing becomes the binding; Escape cancels. A key
+// already bound to another action is still taken, with a warning.
+fn capture_rebind_key(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut capture: ResMut<RebindCapture>,
+    mut bindings: ResMut<Keybindings>
+) {
+    let Some(action) = capture.0 else {
+        return;
+    };
+    let Some(&key) = keys.get_just_pressed().next() else {
+        return;
+    };
+
+    capture.0 = None;
+    if key == KeyCode::Escape {
+        return;
+    }
+
+    if let Some(other) = bindings.action_for(key).filter(|&other| other != action) {
+        warn!("{key:?} is already bound to {other:?}; {action:?} now uses it too");
+    }
+    bindings.rebind(action, key);
+}
+
+fn update_rebind_rows(
+    bindings: Res<Keybindings>,
+    capture: Res<RebindCapture>,
+    rows: Query<(&RebindRow, &Children)>,
+    mut texts: Query<&mut Text>
+) {
+    if !bindings.is_changed() && !capture.is_changed() {
+        return;
+    }
+
+    for (row, children) in &rows {
+        let Some(mut text) = children.first().and_then(|&c| texts.get_mut(c).ok()) else {
+            continue;
+        };
+
+        text.sections[0].value = if capture.0 == Some(row.0) {
+            format!("{:?}: press a key (Esc cancels)", row.0)
+        } else {
+            format!("{:?}: {:?}", row.0, bindings.key(row.0))
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn world_with_rows() -> World {
+        let mut world = World::new();
+        world.init_resource::<ButtonInput<KeyCode>>();
+        world.init_resource::<Keybindings>();
+        world.init_resource::<RebindCapture>();
+        for action in InputAction::ALL {
+            world.spawn((RebindRow(action), Interaction::None));
+        }
+        world
+    }
+
+    fn click(world: &mut World, action: InputAction) {
+        let mut rows = world.query::<(&RebindRow, &mut Interaction)>();
+        for (row, mut interaction) in rows.iter_mut(world) {
+            if row.0 == action {
+                *interaction = Interaction::Pressed;
+            }
+        }
+    }
+
+    fn press(world: &mut World, key: KeyCode) {
+        let mut keys = world.resource_mut::<ButtonInput<KeyCode>>();
+        keys.clear();
+        keys.press(key);
+    }
+
+    fn schedule() -> Schedule {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((start_rebind_capture, capture_rebind_key).chain());
+        schedule
+    }
+
+    #[test]
+    fn clicked_row_takes_the_next_key() {
+        let mut world = world_with_rows();
+        let mut schedule = schedule();
+        schedule.run(&mut world);
+
+        click(&mut world, InputAction::Forward);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<RebindCapture>().0, Some(InputAction::Forward));
+
+        press(&mut world, KeyCode::ArrowUp);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Keybindings>().forward, KeyCode::ArrowUp);
+        assert_eq!(world.resource::<RebindCapture>().0, None);
+    }
+
+    #[test]
+    fn escape_cancels_and_conflicts_still_rebind() {
+        let mut world = world_with_rows();
+        let mut schedule = schedule();
+        schedule.run(&mut world);
+
+        click(&mut world, InputAction::Jump);
+        schedule.run(&mut world);
+        press(&mut world, KeyCode::Escape);
+        schedule.run(&mut world);
+        assert_eq!(world.resource::<Keybindings>().jump, KeyCode::Space);
+        assert_eq!(world.resource::<RebindCapture>().0, None);
+
+        // E is Dash's key: Jump takes it anyway (with a warning)
+        world.resource_mut::<RebindCapture>().0 = Some(InputAction::Jump);
+        press(&mut world, KeyCode::KeyE);
+        schedule.run(&mut world);
+        let bindings = world.resource::<Keybindings>();
+        assert_eq!(bindings.jump, KeyCode::KeyE);
+        assert_eq!(bindings.dash, KeyCode::KeyE);
+    }
+}
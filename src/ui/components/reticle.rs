@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+
+use crate::systems::CameraMode;
+
+pub struct ReticleUiPlugin;
+
+impl Plugin for ReticleUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, setup_reticle);
+        app.add_systems(Update, reticle_visibility);
+    }
+}
+
+// ===== internal =====
+
+const RETICLE_SIZE_PX: f32 = 6.0;
+
+#[derive(Component)]
+struct Reticle;
+
+// A dot at screen center; the first-person aim ray goes through it (player_system).
+fn setup_reticle(mut commands: Commands) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            visibility: Visibility::Hidden,
+            focus_policy: bevy::ui::FocusPolicy::Pass,
+            ..default()
+        })
+        .insert(Reticle)
+        .with_children(|p| {
+            p.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(RETICLE_SIZE_PX),
+                    height: Val::Px(RETICLE_SIZE_PX),
+                    ..default()
+                },
+                background_color: BackgroundColor(Color::srgba(1.0, 1.0, 1.0, 0.8)),
+                border_radius: BorderRadius::MAX,
+                ..default()
+            });
+        });
+}
+
+fn reticle_visibility(mode: Res<CameraMode>, mut q: Query<&mut Visibility, With<Reticle>>) {
+    if !mode.is_changed() {
+        return;
+    }
+
+    for mut vis in &mut q {
+        *vis = if *mode == CameraMode::FirstPerson { Visibility::Inherited } else { Visibility::Hidden };
+    }
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+use crate::systems::{ Health, Player };
+
+/// Red screen-edge overlay that fades in as the player's health drops below
+/// `start_fraction` of max, reaching `max_alpha` at 0 health.
+#[derive(Resource)]
+pub struct LowHealthVignette {
+    pub color: Color,
+    pub max_alpha: f32,
+    pub start_fraction: f32,
+    // thickness of the darkened screen edge
+    pub width_px: f32,
+}
+
+impl Default for LowHealthVignette {
+    fn default() -> Self {
+        Self {
+            color: Color::srgb(0.6, 0.0, 0.0),
+            max_alpha: 0.7,
+            start_fraction: 0.5,
+            width_px: 80.0,
+        }
+    }
+}
+
+impl LowHealthVignette {
+    /// Overlay alpha for `health`: 0 above start_fraction, rising linearly to max_alpha at 0.
+    pub fn alpha(&self, health: &Health) -> f32 {
+        let fraction = health.current / health.max.max(0.001);
+        let t = 1.0 - fraction / self.start_fraction.max(0.001);
+        t.clamp(0.0, 1.0) * self.max_alpha
+    }
+}
+
+pub struct VignetteUiPlugin;
+
+impl Plugin for VignetteUiPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LowHealthVignette>();
+        app.add_systems(Startup, setup_vignette);
+        app.add_systems(Update, update_vignette);
+    }
+}
+
+// ===== internal =====
+
+#[derive(Component)]
+struct Vignette;
+
+fn setup_vignette(mut commands: Commands, settings: Res<LowHealthVignette>) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                border: UiRect::all(Val::Px(settings.width_px)),
+                ..default()
+            },
+            border_color: BorderColor(settings.color.with_alpha(0.0)),
+            // above the HUD, and never in the way of clicks
+            z_index: ZIndex::Global(10),
+            focus_policy: bevy::ui::FocusPolicy::Pass,
+            ..default()
+        },
+        Vignette,
+    ));
+}
+
+fn update_vignette(
+    settings: Res<LowHealthVignette>,
+    player_q: Query<&Health, With<Player>>,
+    mut q: Query<&mut BorderColor, With<Vignette>>
+) {
+    let alpha = player_q.get_single().map_or(0.0, |health| settings.alpha(health));
+    let color = settings.color.with_alpha(alpha);
+
+    for mut border in &mut q {
+        if border.0 != color {
+            border.0 = color;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::ecs::system::RunSystemOnce;
+
+    fn vignette_alpha(world: &mut World) -> f32 {
+        world.run_system_once(update_vignette);
+        let mut q = world.query_filtered::<&BorderColor, With<Vignette>>();
+        q.single(world).0.alpha()
+    }
+
+    #[test]
+    fn vignette_rises_as_health_drops() {
+        let mut world = World::new();
+        world.init_resource::<LowHealthVignette>();
+        world.spawn((Vignette, BorderColor(Color::NONE)));
+        let player = world.spawn((Player, Health::default())).id();
+        let max_alpha = world.resource::<LowHealthVignette>().max_alpha;
+
+        assert_eq!(vignette_alpha(&mut world), 0.0);
+
+        let mut last = 0.0;
+        for current in [40.0, 25.0, 10.0, 0.0] {
+            world.get_mut::<Health>(player).unwrap().current = current;
+            let alpha = vignette_alpha(&mut world);
+            assert!(alpha > last, "{current} health: {alpha} <= {last}");
+            last = alpha;
+        }
+        assert!((last - max_alpha).abs() < 1e-6);
+    }
+}
@@ -1,23 +1,59 @@
 mod components;
 
 use bevy::prelude::*;
-use crate::{ systems::MovementState, ui::components::{ HeartbeatUiPlugin, HeartbeatValue } };
+use crate::{
+    systems::{ movement_system, MovementState },
+    ui::components::{
+        CurveGraphUiPlugin,
+        HeartbeatUiPlugin,
+        HeartbeatValue,
+        JoystickUiPlugin,
+        RebindUiPlugin,
+        ReticleUiPlugin,
+        VignetteUiPlugin,
+    },
+};
 use components::HeartbeatBundle;
 
+// heartbeat sampling rate, independent of render frame rate
+const HEARTBEAT_UPDATE_HZ: f32 = 60.0;
+
 #[derive(Component)]
 struct MovementHudText;
 
+/// What the heartbeat widget plots.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HeartbeatSource {
+    /// Horizontal speed.
+    #[default]
+    Speed,
+    /// |d speed / dt|, highlights bursts and stops instead of steady speed.
+    Acceleration,
+}
+
+// |d speed / dt| over the last FixedUpdate tick, for HeartbeatSource::Acceleration
+#[derive(Resource, Default)]
+struct MovementAccel(f32);
+
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(HeartbeatUiPlugin);
+        app.add_plugins(CurveGraphUiPlugin);
+        app.add_plugins(JoystickUiPlugin);
+        app.add_plugins(RebindUiPlugin);
+        app.add_plugins(ReticleUiPlugin);
+        app.add_plugins(VignetteUiPlugin);
+        app.init_resource::<HeartbeatSource>();
+        app.init_resource::<MovementAccel>();
         app.add_systems(Startup, setup_ui);
+        app.add_systems(FixedUpdate, sample_movement_accel.after(movement_system));
         app.add_systems(Update, (interface_system, update_heartbeat));
     }
 }
 
-fn setup_ui(mut commands: Commands) {
+fn setup_ui(mut commands: Commands, st: Res<MovementState>) {
     commands.spawn((
         TextBundle {
             style: Style {
@@ -57,7 +93,10 @@ fn setup_ui(mut commands: Commands) {
             ..default()
         },
         ..default()
-    });
+    }
+    .with_update_hz(HEARTBEAT_UPDATE_HZ)
+    // flash when speed goes past normal top speed (i.e. sprinting)
+    .with_flash_threshold(st.base_max_speed));
 }
 
 fn interface_system(st: Res<MovementState>, mut q: Query<&mut Text, With<MovementHudText>>) {
@@ -76,7 +115,83 @@ fn interface_system(st: Res<MovementState>, mut q: Query<&mut Text, With<Movemen
     };
 }
 
-fn update_heartbeat(st: Res<MovementState>, mut q: Query<&mut HeartbeatValue>) {
+// Speed changes once per movement tick, so measure it against the fixed timestep
+// (frame dt would read 0 on frames without a tick and spike on frames with several).
+fn sample_movement_accel(
+    time: Res<Time>,
+    st: Res<MovementState>,
+    mut prev_speed: Local<Option<f32>>,
+    mut accel: ResMut<MovementAccel>
+) {
+    let speed = st.velocity.length();
+    let last = prev_speed.replace(speed).unwrap_or(speed);
+
+    let dt = time.delta_seconds();
+    accel.0 = if dt > 0.0 { (speed - last).abs() / dt } else { 0.0 };
+}
+
+fn update_heartbeat(
+    source: Res<HeartbeatSource>,
+    accel: Res<MovementAccel>,
+    st: Res<MovementState>,
+    mut q: Query<&mut HeartbeatValue>
+) {
     let Ok(mut hb) = q.get_single_mut() else { return; };
-    hb.0 = st.velocity.length();
+    hb.0 = match *source {
+        HeartbeatSource::Speed => st.velocity.length(),
+        HeartbeatSource::Acceleration => accel.0,
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::systems::{ InputFrame, ScriptedMove };
+    use std::time::Duration;
+
+    #[test]
+    fn acceleration_uses_the_fixed_timestep() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(1.0 / 64.0));
+        world.insert_resource(time);
+        world.init_resource::<MovementState>();
+        world.init_resource::<MovementAccel>();
+        let sample = world.register_system(sample_movement_accel);
+
+        world.run_system(sample).unwrap();
+        assert_eq!(world.resource::<MovementAccel>().0, 0.0);
+
+        // +0.5 units/s within one 1/64 s tick
+        world.resource_mut::<MovementState>().velocity = Vec2::new(0.0, 0.5);
+        world.run_system(sample).unwrap();
+        assert!((world.resource::<MovementAccel>().0 - 32.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn acceleration_heartbeat_spikes_on_take_off_and_rests_at_steady_speed() {
+        let mut world = World::new();
+        let mut time = Time::<()>::default();
+        time.advance_by(Duration::from_secs_f32(1.0 / 64.0));
+        world.insert_resource(time);
+        world.insert_resource(HeartbeatSource::Acceleration);
+        world.init_resource::<MovementState>();
+        world.init_resource::<MovementAccel>();
+        world.init_resource::<Events<ScriptedMove>>();
+        world.insert_resource(InputFrame { move_dir: Vec2::Y, ..default() });
+        let hb = world.spawn(HeartbeatValue(0.0)).id();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((movement_system, sample_movement_accel, update_heartbeat).chain());
+
+        let mut values = Vec::new();
+        for _ in 0..128 {
+            schedule.run(&mut world);
+            values.push(world.get::<HeartbeatValue>(hb).unwrap().0);
+        }
+
+        let peak = values[..8].iter().copied().fold(0.0, f32::max);
+        assert!(peak > 10.0, "{values:?}");
+        // two seconds in the speed has flattened out
+        assert!(values[127] < 0.01, "{values:?}");
+    }
 }